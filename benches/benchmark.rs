@@ -0,0 +1,69 @@
+//! Benchmarks for the public API over small, medium and large documents.
+//! These numbers are the baseline to compare against when changing how scriptures are found.
+
+mod common;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use est::locales::nwt_en::Site;
+use std::hint::black_box;
+
+fn surround_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("surround");
+
+    for (size, text) in common::documents() {
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &text, |b, text| {
+            b.iter(|| est::surround(black_box(text.as_str()), "<b>", "</b>").unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn url_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("url");
+
+    for (size, text) in common::documents() {
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &text, |b, text| {
+            b.iter(|| est::url(&Site::JwOrg, black_box(text.as_str())).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn get_scriptures_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_scriptures");
+
+    for (size, text) in common::documents() {
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &text, |b, text| {
+            b.iter(|| est::get_scriptures(black_box(text.as_str())).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn get_locations_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_locations");
+
+    for (size, text) in common::documents() {
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &text, |b, text| {
+            b.iter(|| est::get_locations(black_box(text.as_str())))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    surround_bench,
+    url_bench,
+    get_scriptures_bench,
+    get_locations_bench
+);
+criterion_main!(benches);
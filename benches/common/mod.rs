@@ -0,0 +1,24 @@
+//! Generated documents shared by the benchmarks.
+// Not every benchmark uses every document.
+#![allow(dead_code)]
+
+const PARAGRAPHS: [&str; 4] = [
+    "Our theme text for today is found at John 3:16, a scripture that many of you know by heart. ",
+    "Notice how Psalms 83:18 and Matthew 24:14 point to the same hope, while Re 21:3-4 tells us what the future holds. ",
+    "When we feel anxious, Philippians 4:6, 7 reminds us to pray and 1 Peter 5:7 invites us to throw our anxiety on him. ",
+    "Let us close by reading Proverbs 17:17 together and thinking about how we can be a true friend this week.\n",
+];
+
+/// Builds a sermon transcript made of `paragraphs` paragraphs, most of which contain scriptures.
+pub fn sermon(paragraphs: usize) -> String {
+    PARAGRAPHS.iter().cycle().take(paragraphs).copied().collect()
+}
+
+/// The documents benchmarked by the suite, from a single sentence to roughly a megabyte of text.
+pub fn documents() -> [(&'static str, String); 3] {
+    [
+        ("small", sermon(1)),
+        ("medium", sermon(100)),
+        ("large", sermon(10_000)),
+    ]
+}
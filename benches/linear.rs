@@ -0,0 +1,31 @@
+//! Shows that linking and surrounding scale linearly with the size of the text.
+//! The throughput reported for each size should stay roughly the same as the corpus grows.
+
+mod common;
+
+use common::sermon;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use est::locales::nwt_en::Site;
+use std::hint::black_box;
+
+fn linear_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("linear");
+
+    for paragraphs in [100, 1_000, 10_000] {
+        let text: String = sermon(paragraphs);
+        group.throughput(Throughput::Bytes(text.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("url", paragraphs), &text, |b, text| {
+            b.iter(|| est::url(&Site::JwOrg, black_box(text.as_str())).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("surround", paragraphs), &text, |b, text| {
+            b.iter(|| est::surround(black_box(text.as_str()), "<b>", "</b>").unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, linear_bench);
+criterion_main!(benches);
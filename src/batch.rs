@@ -0,0 +1,105 @@
+//! Processes many documents at once, spreading them over every CPU core with [rayon](https://docs.rs/rayon).
+//!
+//! Only available with the `rayon` feature. Each function takes a collection of documents and returns one
+//! output per document, in the same order as the documents were given. Functions that can fail return a result
+//! per document, so an error in one document does not stop the others from being processed. The book names are compiled once and shared by every thread.
+use std::borrow::Cow;
+
+use rayon::prelude::*;
+
+use crate::{
+    locales::{nwt_en::Site, BibleError},
+    parsers::surround::{Script, ScriptureCollection},
+};
+
+/// Same as [`crate::surround`] for each document.
+///
+/// ## Example
+/// ```
+/// let notes: Vec<&str> = vec!["Read John 3:16.", "No scripture here.", "Ge 1:1"];
+/// let got: Vec<String> = est::batch::surround(notes, "**", "**");
+/// assert_eq!(got, vec!["Read **John 3:16**.", "No scripture here.", "**Ge 1:1**"]);
+/// ```
+pub fn surround<'a, I, S>(documents: I, prefix: &str, postfix: &str) -> Vec<String>
+where
+    I: IntoParallelIterator<Item = S>,
+    S: Into<Cow<'a, str>>,
+{
+    documents
+        .into_par_iter()
+        .map(|document| {
+            Script::new(document)
+                .prefix(prefix)
+                .postfix(postfix)
+                .surround()
+                .get_text()
+        })
+        .collect()
+}
+
+/// Same as [`crate::url`] for each document.
+///
+/// ## Example
+/// ```
+/// use est::locales::{nwt_en::Site::JwOrg, BibleError};
+///
+/// let notes: Vec<String> = vec!["Read Joh 3:16.".to_string(), "Read Mary 1:1.".to_string()];
+/// let got = est::batch::url(&JwOrg, notes);
+/// assert_eq!(got[0], Ok("Read [Joh 3:16](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016).".to_string()));
+/// assert_eq!(got[1], Err(BibleError::BookNotFound("Mary".to_string())));
+/// ```
+pub fn url<'a, I, S>(site: &Site, documents: I) -> Vec<Result<String, BibleError>>
+where
+    I: IntoParallelIterator<Item = S>,
+    S: Into<Cow<'a, str>>,
+{
+    documents
+        .into_par_iter()
+        .map(|document| Ok(Script::new(document).url(site)?.get_text()))
+        .collect()
+}
+
+/// Same as [`crate::get_scriptures`] for each document.
+///
+/// ## Example
+/// ```
+/// let notes: Vec<&str> = vec!["John 3:16 and Re 21:4", "Nothing"];
+/// let got = est::batch::get_scriptures(notes);
+/// assert_eq!(got[0], Ok(vec!["John 3:16".to_string(), "Re 21:4".to_string()]));
+/// assert_eq!(got[1], Ok(vec![]));
+/// ```
+pub fn get_scriptures<'a, I, S>(documents: I) -> Vec<Result<ScriptureCollection, BibleError>>
+where
+    I: IntoParallelIterator<Item = S>,
+    S: Into<Cow<'a, str>>,
+{
+    documents
+        .into_par_iter()
+        .map(|document| Script::new(document).get_scriptures())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn t_same_order_as_documents() {
+        let documents: Vec<String> = (1..=200)
+            .map(|verse| format!("Read John 3:{verse}."))
+            .collect();
+        let expect: Vec<String> = documents
+            .iter()
+            .map(|document| crate::surround(document.as_str(), "<", ">").unwrap())
+            .collect();
+        assert_eq!(surround(&documents, "<", ">"), expect);
+    }
+
+    #[test]
+    fn t_error_per_document() {
+        let got = url(&Site::JwOrg, vec!["Mary 1:1", "John 3:16"]);
+        assert_eq!(got[0], Err(BibleError::BookNotFound("Mary".to_string())));
+        assert!(got[1].is_ok());
+    }
+}
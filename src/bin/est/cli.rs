@@ -0,0 +1,273 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use est::locales::nwt_en::Site;
+use est::{LinkFormat, Style};
+
+/// Scans text for Bible references and links or highlights them.
+#[derive(Debug, Parser)]
+#[command(name = "est", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Lists the references found, one per line as `file:line:column: reference` or as JSON.
+    Scan(ScanArgs),
+    /// Links each reference to an online Bible.
+    Link(LinkArgs),
+    /// Wraps each reference with a prefix and a postfix.
+    Surround(SurroundArgs),
+    /// Rewrites each reference in a single style, `joh 3:16—18` becomes `John 3:16-18`.
+    Normalize(NormalizeArgs),
+    /// Reports unknown or ambiguous books, chapters and verses that do not exist and reversed ranges, exits
+    /// with an error if any is found.
+    Check(CheckArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ScanArgs {
+    #[command(flatten)]
+    pub input: Input,
+
+    /// Also list the references whose book is not known.
+    #[arg(long)]
+    pub all: bool,
+
+    /// How the references are listed.
+    #[arg(long, short, value_enum, default_value_t = ScanOutput::Text)]
+    pub output: ScanOutput,
+
+    /// The online Bible the URLs in JSON output link to.
+    #[arg(long, value_enum, default_value_t = SiteArg::JwOrg)]
+    pub site: SiteArg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ScanOutput {
+    /// `file:line:column: reference`
+    Text,
+    /// A JSON array with everything known about each reference.
+    Json,
+    /// One JSON object per line.
+    Ndjson,
+}
+
+#[derive(Debug, Args)]
+pub struct CheckArgs {
+    #[command(flatten)]
+    pub input: Input,
+
+    /// How the problems are reported.
+    #[arg(long, short, value_enum, default_value_t = CheckOutput::Text)]
+    pub output: CheckOutput,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CheckOutput {
+    /// `file:line:column: error: message`, like a compiler.
+    Text,
+    /// A SARIF 2.1.0 log, as read by code scanning tools.
+    Sarif,
+}
+
+#[derive(Debug, Args)]
+pub struct LinkArgs {
+    #[command(flatten)]
+    pub input: Input,
+
+    #[command(flatten)]
+    pub output: Output,
+
+    /// The online Bible to link to.
+    #[arg(long, value_enum, default_value_t = SiteArg::JwOrg)]
+    pub site: SiteArg,
+
+    /// The markup of the links.
+    #[arg(long, short, value_enum, default_value_t = Format::Markdown)]
+    pub format: Format,
+
+    /// The note template used by `--format wiki`, see `est::WikiLink`.
+    #[arg(long, default_value = "{BOOK} {CHAPTER}#{VERSE}")]
+    pub wiki_template: String,
+}
+
+#[derive(Debug, Args)]
+pub struct SurroundArgs {
+    #[command(flatten)]
+    pub input: Input,
+
+    #[command(flatten)]
+    pub output: Output,
+
+    /// The text added before each reference.
+    #[arg(long, default_value = "")]
+    pub prefix: String,
+
+    /// The text added after each reference.
+    #[arg(long, default_value = "")]
+    pub postfix: String,
+}
+
+#[derive(Debug, Args)]
+pub struct NormalizeArgs {
+    #[command(flatten)]
+    pub input: Input,
+
+    #[command(flatten)]
+    pub output: Output,
+
+    /// How each reference is written.
+    #[arg(long, short, value_enum, default_value_t = StyleArg::Full)]
+    pub style: StyleArg,
+}
+
+/// Where the text is read from and how it is interpreted.
+#[derive(Debug, Args)]
+pub struct Input {
+    /// The files to read, standard input is read when none are given or for `-`. Directories are read
+    /// recursively, skipping hidden files and directories.
+    pub files: Vec<PathBuf>,
+
+    /// Only reads the files in directories matching this glob, i.e. `*.md`, can be repeated.
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Does not read the files in directories matching this glob, i.e. `drafts/**`, can be repeated.
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// How the text is interpreted, references inside code, links and markup are left alone for Markdown and HTML.
+    #[arg(long, value_enum, default_value_t = InputMode::Text)]
+    pub input: InputMode,
+
+    /// The language of the text.
+    #[arg(long, value_enum, default_value_t = LocaleArg::En)]
+    pub locale: LocaleArg,
+
+    /// Rewrites book names to their canonical form, `JOH 3:16` becomes `John 3:16`.
+    #[arg(long)]
+    pub normalize: bool,
+
+    /// Ignores diacritics in book names, `Génesis` is read as `Genesis`.
+    #[arg(long)]
+    pub ignore_diacritics: bool,
+
+    /// Also reads the USFM/Paratext book codes, such as `JHN 3:16` and `1CO 13:4`.
+    #[arg(long)]
+    pub usfm: bool,
+
+    /// Also reads the SBL abbreviations of the books, such as `Gen 1:1` and `Rom 8:28`.
+    #[arg(long)]
+    pub sbl: bool,
+}
+
+/// Where the result is written.
+#[derive(Debug, Args)]
+pub struct Output {
+    /// Rewrites each file instead of writing to standard output.
+    #[arg(long, short)]
+    pub in_place: bool,
+
+    /// The suffix of the backup made of each file rewritten in place.
+    #[arg(long, default_value = ".bak")]
+    pub backup_suffix: String,
+
+    /// Does not make a backup of the files rewritten in place.
+    #[arg(long)]
+    pub no_backup: bool,
+
+    /// Prints what would change as a unified diff instead of writing anything.
+    #[arg(long, conflicts_with = "in_place")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InputMode {
+    Text,
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LocaleArg {
+    /// English, New World Translation.
+    #[value(alias = "en_us")]
+    En,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SiteArg {
+    /// https://www.jw.org
+    JwOrg,
+}
+
+impl From<SiteArg> for Site {
+    fn from(site: SiteArg) -> Self {
+        match site {
+            SiteArg::JwOrg => Site::JwOrg,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StyleArg {
+    /// `1 Corinthians 13:4-7`
+    Full,
+    /// `1Co 13:4-7`
+    Nwt,
+    /// `1 Cor 13:4–7`
+    Sbl,
+    /// `1Co 13:4-7,13`
+    Compact,
+    /// `1Cor.13.4-1Cor.13.7`
+    Osis,
+    /// `1CO 13:4-7`
+    Usfm,
+}
+
+impl From<StyleArg> for Style {
+    fn from(style: StyleArg) -> Self {
+        match style {
+            StyleArg::Full => Style::Full,
+            StyleArg::Nwt => Style::Nwt,
+            StyleArg::Sbl => Style::Sbl,
+            StyleArg::Compact => Style::Compact,
+            StyleArg::Osis => Style::Osis,
+            StyleArg::Usfm => Style::Usfm,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Markdown,
+    Html,
+    #[value(name = "asciidoc")]
+    AsciiDoc,
+    #[value(name = "rst")]
+    RestructuredText,
+    Org,
+    Latex,
+    #[value(name = "bbcode")]
+    BBCode,
+    /// Obsidian and Logseq wiki links.
+    Wiki,
+}
+
+impl Format {
+    /// The format as a [`LinkFormat`], `None` for the formats that are not plain links.
+    pub fn link_format(self) -> Option<LinkFormat> {
+        match self {
+            Format::Markdown => Some(LinkFormat::Markdown),
+            Format::AsciiDoc => Some(LinkFormat::AsciiDoc),
+            Format::RestructuredText => Some(LinkFormat::RestructuredText),
+            Format::Org => Some(LinkFormat::Org),
+            Format::Latex => Some(LinkFormat::Latex),
+            Format::BBCode => Some(LinkFormat::BBCode),
+            Format::Html | Format::Wiki => None,
+        }
+    }
+}
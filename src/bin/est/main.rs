@@ -0,0 +1,338 @@
+//! The `est` command-line tool, built with the `cli` feature.
+mod cli;
+mod report;
+mod walk;
+
+use std::{
+    ffi::OsString,
+    fs,
+    io::{self, Write},
+    process::ExitCode,
+};
+
+use clap::Parser;
+use cli::{
+    CheckArgs, CheckOutput, Cli, Command, Format, Input, InputMode, LinkArgs, LocaleArg,
+    NormalizeArgs, Output, ScanArgs, ScanOutput, SurroundArgs,
+};
+use est::{
+    locales::{nwt_en::Site, BibleError},
+    Anchor, Locations, Match, Script, WikiLink,
+};
+use report::Record;
+use similar::{ChangeTag, TextDiff};
+use walk::Source;
+
+fn main() -> ExitCode {
+    let cli: Cli = Cli::parse();
+
+    let ok: bool = match &cli.command {
+        Command::Scan(args) => scan(args),
+        Command::Link(args) => rewrite(&args.input, &args.output, |text| link(text, args)),
+        Command::Surround(args) => rewrite(&args.input, &args.output, |text| surround(text, args)),
+        Command::Normalize(args) => {
+            rewrite(&args.input, &args.output, |text| normalize(text, args))
+        }
+        Command::Check(args) => check(args),
+    };
+
+    match ok {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+/// Builds the `Script` for `text` with the options shared by every subcommand.
+fn script<'a>(input: &Input, text: &'a str) -> Script<'a> {
+    // Book names are looked up in the only locale there is so far.
+    let mut script: Script = match input.locale {
+        LocaleArg::En => Script::new(text),
+    };
+
+    script = match input.input {
+        InputMode::Text => script,
+        InputMode::Markdown => script.markdown(),
+        InputMode::Html => script.html_document(),
+    };
+    if input.normalize {
+        script = script.normalize();
+    }
+    if input.ignore_diacritics {
+        script = script.ignore_diacritics();
+    }
+    if input.usfm {
+        script = script.usfm();
+    }
+    if input.sbl {
+        script = script.sbl();
+    }
+
+    script
+}
+
+fn link(text: &str, args: &LinkArgs) -> Result<String, BibleError> {
+    let script: Script = script(&args.input, text);
+    let site = args.site.into();
+
+    let script: Script = match (args.format, args.format.link_format()) {
+        (_, Some(link_format)) => script.link_format(link_format).url(&site)?,
+        (Format::Html, None) => script.html(&site, &Anchor::new())?,
+        (_, None) => script.wiki(&WikiLink::new().template(&args.wiki_template))?,
+    };
+
+    Ok(script.get_text())
+}
+
+fn surround(text: &str, args: &SurroundArgs) -> Result<String, BibleError> {
+    Ok(script(&args.input, text)
+        .prefix(&args.prefix)
+        .postfix(&args.postfix)
+        .surround()
+        .get_text())
+}
+
+fn normalize(text: &str, args: &NormalizeArgs) -> Result<String, BibleError> {
+    Ok(script(&args.input, text)
+        .style(args.style.into())
+        .surround()
+        .get_text())
+}
+
+/// Returns the sources named on the command line, printing why the others could not be found. Files ending
+/// with `backup` are left out of the directories walked.
+fn sources(input: &Input, backup: Option<&str>, ok: &mut bool) -> Vec<Source> {
+    match walk::sources(input, backup) {
+        Ok(sources) => sources
+            .into_iter()
+            .filter_map(|source| {
+                source
+                    .inspect_err(|e| {
+                        eprintln!("est: {e}");
+                        *ok = false;
+                    })
+                    .ok()
+            })
+            .collect(),
+        Err(e) => {
+            eprintln!("est: {e}");
+            *ok = false;
+            Vec::new()
+        }
+    }
+}
+
+/// Reads the text of `source`, printing why it could not be read, e.g. it is not UTF-8. Binary files are skipped.
+fn read(source: &Source, ok: &mut bool) -> Option<String> {
+    match source.read() {
+        Ok(Some(text)) => Some(text),
+        Ok(None) => {
+            eprintln!("est: {source}: skipping binary file");
+            None
+        }
+        Err(e) => {
+            eprintln!("est: {source}: {e}");
+            *ok = false;
+            None
+        }
+    }
+}
+
+/// Prints the references found in each source, returns `false` if a source could not be read.
+fn scan(args: &ScanArgs) -> bool {
+    let mut ok: bool = true;
+    let mut records: Vec<Record> = Vec::new();
+    let mut stdout = io::stdout().lock();
+    let site: Site = args.site.into();
+
+    for source in sources(&args.input, None, &mut ok) {
+        let Some(text) = read(&source, &mut ok) else {
+            continue;
+        };
+
+        let script: Script = script(&args.input, &text);
+        let found: Vec<Match> = script
+            .references()
+            .filter(|found| args.all || found.is_valid())
+            .collect();
+        let locations: Locations = Locations {
+            slices: found.iter().map(Match::slice).collect(),
+            string: text.clone(),
+        };
+
+        for (found, span) in found.iter().zip(locations.spans()) {
+            let line: String = match args.output {
+                ScanOutput::Text => {
+                    let unknown: &str = match found.is_valid() {
+                        true => "",
+                        false => " (unknown book)",
+                    };
+                    format!(
+                        "{source}:{}:{}: {}{unknown}",
+                        span.start.line,
+                        span.start.column,
+                        found.as_str()
+                    )
+                }
+                ScanOutput::Json => {
+                    records.push(Record::new(source.to_string(), found, span, &site));
+                    continue;
+                }
+                ScanOutput::Ndjson => {
+                    let record: Record = Record::new(source.to_string(), found, span, &site);
+                    serde_json::to_string(&record).expect("a record is always valid JSON")
+                }
+            };
+            if writeln!(stdout, "{line}").is_err() {
+                return ok;
+            }
+        }
+    }
+
+    if args.output == ScanOutput::Json {
+        let json: String =
+            serde_json::to_string_pretty(&records).expect("a record is always valid JSON");
+        // A closed standard output is not an error worth reporting.
+        let _ = writeln!(stdout, "{json}");
+    }
+
+    ok
+}
+
+/// Reports the problems with the references in each source, returns `false` if any was found or a source
+/// could not be read.
+fn check(args: &CheckArgs) -> bool {
+    let mut ok: bool = true;
+    let mut problems: usize = 0;
+    let mut results: Vec<serde_json::Value> = Vec::new();
+    let mut stdout = io::stdout().lock();
+
+    for source in sources(&args.input, None, &mut ok) {
+        let Some(text) = read(&source, &mut ok) else {
+            continue;
+        };
+
+        let script: Script = script(&args.input, &text);
+        let found: Vec<(Match, Vec<BibleError>)> = script
+            .references()
+            .map(|found| (found, found.check()))
+            .filter(|(_, errors)| !errors.is_empty())
+            .collect();
+        let locations: Locations = Locations {
+            slices: found.iter().map(|(found, _)| found.slice()).collect(),
+            string: text.clone(),
+        };
+
+        for ((_, errors), span) in found.iter().zip(locations.spans()) {
+            for error in errors {
+                problems += 1;
+                match args.output {
+                    CheckOutput::Text => {
+                        let (line, column) = (span.start.line, span.start.column);
+                        // A closed standard output still fails the check below.
+                        let _ = writeln!(stdout, "{source}:{line}:{column}: error: {error}");
+                    }
+                    CheckOutput::Sarif => {
+                        results.push(report::sarif_result(&source.to_string(), &span, error))
+                    }
+                }
+            }
+        }
+    }
+
+    if args.output == CheckOutput::Sarif {
+        let sarif: String =
+            serde_json::to_string_pretty(&report::sarif(results)).expect("SARIF is valid JSON");
+        let _ = writeln!(stdout, "{sarif}");
+    }
+    if problems > 0 {
+        eprintln!("est: {problems} problem{} found", plural(problems));
+    }
+
+    ok && problems == 0
+}
+
+/// Applies `transform` to each source and writes the result to standard output or back to the file,
+/// returns `false` if any source failed. When rewriting in place or on a dry run the number of lines
+/// changed in each file is reported.
+fn rewrite<F>(input: &Input, output: &Output, transform: F) -> bool
+where
+    F: Fn(&str) -> Result<String, BibleError>,
+{
+    let mut ok: bool = true;
+    let report: bool = output.in_place || output.dry_run;
+    let (mut files, mut changed): (usize, usize) = (0, 0);
+
+    // The backups of an earlier run are not rewritten again.
+    for source in sources(input, Some(&output.backup_suffix), &mut ok) {
+        let Some(text) = read(&source, &mut ok) else {
+            continue;
+        };
+        let result: Result<usize, String> =
+            transform(&text)
+                .map_err(|e| e.to_string())
+                .and_then(|transformed| {
+                    write(&source, output, &text, &transformed).map_err(|e| e.to_string())
+                });
+
+        match result {
+            Ok(lines) => {
+                files += 1;
+                if report && lines > 0 {
+                    changed += 1;
+                    eprintln!("est: {source}: {lines} line{} changed", plural(lines));
+                }
+            }
+            Err(e) => {
+                eprintln!("est: {source}: {e}");
+                ok = false;
+            }
+        }
+    }
+
+    if report {
+        eprintln!("est: {changed} of {files} file{} changed", plural(files));
+    }
+
+    ok
+}
+
+fn plural(count: usize) -> &'static str {
+    match count {
+        1 => "",
+        _ => "s",
+    }
+}
+
+/// Writes `transformed` to standard output, over the file when rewriting in place or as a unified diff on a
+/// dry run. Files that did not change are not touched. Returns the number of lines changed.
+fn write(source: &Source, output: &Output, text: &str, transformed: &str) -> io::Result<usize> {
+    let diff = TextDiff::from_lines(text, transformed);
+    let lines: usize = diff
+        .iter_all_changes()
+        .filter(|change| change.tag() == ChangeTag::Insert)
+        .count();
+
+    match (source, output.in_place) {
+        _ if output.dry_run => {
+            if lines > 0 {
+                let name: String = source.to_string();
+                let mut unified = diff.unified_diff();
+                write!(io::stdout().lock(), "{}", unified.header(&name, &name))?;
+            }
+        }
+        (Source::File(path), true) => {
+            if lines == 0 {
+                return Ok(0);
+            }
+            if !output.no_backup {
+                let mut backup: OsString = path.as_os_str().to_owned();
+                backup.push(&output.backup_suffix);
+                fs::copy(path, backup)?;
+            }
+            fs::write(path, transformed)?;
+        }
+        _ => io::stdout().lock().write_all(transformed.as_bytes())?,
+    }
+
+    Ok(lines)
+}
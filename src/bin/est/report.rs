@@ -0,0 +1,139 @@
+use est::{
+    locales::{nwt_en::Site, BibleError},
+    Match, Position, Reference, Span, VerseRange,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// The rules reported by `est check`, with their description.
+const RULES: [(&str, &str); 6] = [
+    ("unknown-book", "The Bible book does not exist."),
+    (
+        "ambiguous-book",
+        "The abbreviation could be more than one Bible book.",
+    ),
+    ("chapter-not-found", "The book has no such chapter."),
+    ("verse-not-found", "The chapter has no such verse."),
+    (
+        "invalid-range",
+        "The range of verses ends before it starts.",
+    ),
+    ("malformed-reference", "The reference could not be parsed."),
+];
+
+/// _Record_ is everything known about a reference found by `est scan`, written as JSON.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    /// The file the reference was found in, `<stdin>` for standard input.
+    pub file: String,
+    /// The reference as it appears in the text.
+    pub text: String,
+    pub start: Position,
+    pub end: Position,
+    /// True if the book of the reference exists.
+    pub valid: bool,
+    /// The number of the book, 1 for Genesis through 66 for Revelation.
+    pub book: Option<u8>,
+    /// The canonical name of the book.
+    pub book_name: Option<&'static str>,
+    /// The first chapter.
+    pub chapter: Option<u16>,
+    /// The verses in the first chapter.
+    pub verses: Vec<VerseRange>,
+    /// Every chapter of the reference, `John 3:16; 4:1` has two.
+    pub references: Vec<Reference>,
+    /// The reference as OSIS, `John.3.16 John.4.1`.
+    pub osis: Option<String>,
+    pub url: Option<String>,
+    /// Why the reference is not valid.
+    pub error: Option<String>,
+}
+
+impl Record {
+    pub fn new(file: String, found: &Match, span: Span, site: &Site) -> Self {
+        let (references, error) = match found.references() {
+            Ok(references) => (references, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        let first: Option<&Reference> = references.first();
+
+        Self {
+            file,
+            text: found.as_str().to_string(),
+            start: span.start,
+            end: span.end,
+            valid: error.is_none(),
+            book: first.map(|reference| reference.book as u8),
+            book_name: first.map(|reference| reference.book.canonical_name()),
+            chapter: first.map(|reference| reference.chapter),
+            verses: first
+                .map(|reference| reference.verses.clone())
+                .unwrap_or_default(),
+            url: found.url(site).ok(),
+            osis: first.map(|_| {
+                let ids: Vec<String> = references.iter().map(Reference::to_osis).collect();
+                ids.join(" ")
+            }),
+            references,
+            error,
+        }
+    }
+}
+
+/// Returns the id of the rule `error` breaks, one of [`RULES`].
+fn rule(error: &BibleError) -> &'static str {
+    match error {
+        BibleError::BookNotFound(_) => "unknown-book",
+        BibleError::AmbiguousBook(..) => "ambiguous-book",
+        BibleError::ChapterNotFound(..) => "chapter-not-found",
+        BibleError::VerseNotFound(..) => "verse-not-found",
+        BibleError::InvalidRange(_) => "invalid-range",
+        BibleError::ParsingError(_) => "malformed-reference",
+    }
+}
+
+/// Returns a SARIF result for `error` found at `span` of `file`.
+pub fn sarif_result(file: &str, span: &Span, error: &BibleError) -> Value {
+    json!({
+        "ruleId": rule(error),
+        "level": "error",
+        "message": { "text": error.to_string() },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": file },
+                "region": {
+                    "startLine": span.start.line,
+                    "startColumn": span.start.column,
+                    "endLine": span.end.line,
+                    "endColumn": span.end.column,
+                },
+            },
+        }],
+    })
+}
+
+/// Returns a SARIF 2.1.0 log with `results` for a single run of `est check`.
+pub fn sarif(results: Vec<Value>) -> Value {
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|(id, description)| json!({ "id": id, "shortDescription": { "text": description } }))
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "est",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                },
+            },
+            // Columns are counted in chars, see `est::Position`.
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+}
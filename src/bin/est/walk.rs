@@ -0,0 +1,115 @@
+use std::{
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use walkdir::{DirEntry, WalkDir};
+
+use crate::cli::Input;
+
+/// How many bytes are looked at for a NUL byte to tell a binary file from a text file.
+const SNIFF: usize = 8000;
+
+/// Where a text is read from.
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl Source {
+    /// Reads the text, `None` for a binary file. Text that is not UTF-8 is an `InvalidData` error.
+    pub fn read(&self) -> io::Result<Option<String>> {
+        let mut bytes: Vec<u8> = Vec::new();
+        match self {
+            Source::Stdin => io::stdin().read_to_end(&mut bytes)?,
+            Source::File(path) => fs::File::open(path)?.read_to_end(&mut bytes)?,
+        };
+
+        if bytes[..bytes.len().min(SNIFF)].contains(&0) {
+            return Ok(None);
+        }
+        String::from_utf8(bytes)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Returns the sources named on the command line, standard input when there are none. Directories are
+/// walked recursively for the files matching `--include` and not `--exclude`, hidden files and
+/// directories are skipped, as are the files ending with `backup`, the suffix of the backups `est` makes.
+pub fn sources(input: &Input, backup: Option<&str>) -> Result<Vec<Result<Source, String>>, String> {
+    if input.files.is_empty() {
+        return Ok(vec![Ok(Source::Stdin)]);
+    }
+    let include: Option<GlobSet> = match input.include.is_empty() {
+        true => None,
+        false => Some(glob_set(&input.include)?),
+    };
+    let exclude: GlobSet = glob_set(&input.exclude)?;
+
+    let mut sources: Vec<Result<Source, String>> = Vec::new();
+    for file in &input.files {
+        if file.as_os_str() == "-" {
+            sources.push(Ok(Source::Stdin));
+            continue;
+        }
+        if !file.is_dir() {
+            sources.push(Ok(Source::File(file.clone())));
+            continue;
+        }
+
+        let walk = WalkDir::new(file)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_hidden(entry));
+        for entry in walk {
+            let entry: DirEntry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    sources.push(Err(e.to_string()));
+                    continue;
+                }
+            };
+            if !entry.file_type().is_file() || is_backup(&entry, backup) {
+                continue;
+            }
+            // Globs are matched against the path below the directory named on the command line.
+            let relative: &Path = entry.path().strip_prefix(file).unwrap_or(entry.path());
+            let included: bool = include.as_ref().is_none_or(|set| set.is_match(relative));
+            if included && !exclude.is_match(relative) {
+                sources.push(Ok(Source::File(entry.into_path())));
+            }
+        }
+    }
+
+    Ok(sources)
+}
+
+fn glob_set(globs: &[String]) -> Result<GlobSet, String> {
+    let mut builder: GlobSetBuilder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(Glob::new(glob).map_err(|e| e.to_string())?);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+fn is_hidden(entry: &DirEntry) -> bool {
+    entry.file_name().to_string_lossy().starts_with('.')
+}
+
+fn is_backup(entry: &DirEntry, backup: Option<&str>) -> bool {
+    backup.is_some_and(|suffix| {
+        !suffix.is_empty() && entry.file_name().to_string_lossy().ends_with(suffix)
+    })
+}
@@ -29,12 +29,23 @@
 //!Contributions are welcomed, but please be aware that the project is still in its prototype phase and large portions of code might change at any moment.
 //!Feel free to open an issue if you have any questions or suggestions.
 
+#[cfg(feature = "rayon")]
+pub mod batch;
 pub mod locales;
 mod parsers;
 mod url;
+use std::borrow::Cow;
+use std::io::{self, Read, Write};
 use locales::nwt_en::Site;
 use locales::BibleError;
-pub use parsers::surround::{ScriptSlice, ScriptureCollection, Locations};
+pub use parsers::anchor::Anchor;
+pub use parsers::format::LinkFormat;
+pub use parsers::matches::{Match, References};
+pub use parsers::position::{Position, Span};
+pub use parsers::reference::{Reference, VerseRange};
+pub use parsers::style::{Style, Styled};
+pub use parsers::surround::{Locations, Script, ScriptSlice, ScriptureCollection};
+pub use parsers::wiki::WikiLink;
 
 
 #[allow(non_camel_case_types)]
@@ -57,7 +68,7 @@ pub enum Locale {
 /// assert_eq!(expected, surround(text, "<strong>", "</strong>").unwrap());
 /// 
 /// ```
-pub fn surround<'a, S: Into<String> + Clone>(
+pub fn surround<'a, S: Into<Cow<'a, str>>>(
     text: S,
     prefix: &'a str,
     postfix: &'a str,
@@ -69,6 +80,48 @@ pub fn surround<'a, S: Into<String> + Clone>(
         .get_text())
 }
 
+/// Same as [`surround`], but the book name of each valid scripture is also rewritten to the locale's canonical form.
+/// Scriptures are detected regardless of their casing, which makes this useful for headings and slide titles.
+///
+/// ## Example
+/// ```
+/// use est::surround_normalized;
+///
+/// let text: &str = "JOHN 3:16 and 1 CORINTHIANS 13:4-7";
+/// let expected: String = "<strong>John 3:16</strong> and <strong>1 Corinthians 13:4-7</strong>".to_string();
+/// assert_eq!(expected, surround_normalized(text, "<strong>", "</strong>").unwrap());
+/// ```
+pub fn surround_normalized<'a, S: Into<Cow<'a, str>>>(
+    text: S,
+    prefix: &'a str,
+    postfix: &'a str,
+) -> Result<String, BibleError> {
+    Ok(parsers::surround::Script::new(text)
+        .prefix(prefix)
+        .postfix(postfix)
+        .normalize()
+        .surround()
+        .get_text())
+}
+
+/// Rewrites each valid scripture in `style`, the book name as well as the dashes and spacing of the chapter and
+/// verses, leaving the text around it untouched. Scriptures whose book is not known are left as they are.
+///
+/// ## Example
+/// ```
+/// use est::Style;
+///
+/// let text: &str = "Compare Joh 3:16, john 3:16 and JOHN 3:16–18,20.";
+/// let expected: &str = "Compare Joh 3:16, Joh 3:16 and Joh 3:16-18, 20.";
+/// assert_eq!(expected, est::normalize(text, Style::Nwt).unwrap());
+/// ```
+pub fn normalize<'a, S: Into<Cow<'a, str>>>(text: S, style: Style) -> Result<String, BibleError> {
+    Ok(parsers::surround::Script::new(text)
+        .style(style)
+        .surround()
+        .get_text())
+}
+
 /// Adds Markdown link syntax around found scriptures to an Online Bible and the modified string is returned.
 /// When possible, it will link directly to the scripture being referenced.
 /// 
@@ -85,13 +138,66 @@ pub fn surround<'a, S: Into<String> + Clone>(
 /// let expected:String = "All friends should practice [Proverbs 17:17](https://www.jw.org/en/library/bible/study-bible/books/proverbs/17/#v20017017)!".to_string();
 /// assert_eq!(expected, est::url(&JwOrg, text).unwrap());
 /// ```
-pub fn url<S: Into<String> + Clone>(site: &Site, text: S) -> Result<String, BibleError> {
+pub fn url<'a, S: Into<Cow<'a, str>>>(site: &Site, text: S) -> Result<String, BibleError> {
     // TODO: Flip the order of the paramaters around, text should be first to follow the pattern set with the other functions.
     Ok(parsers::surround::Script::new(text)
         .url(site)?
         .get_text())
 }
 
+/// Same as [`url`], but the book name of each scripture is also rewritten to the locale's canonical form.
+///
+/// ## Example
+/// ```
+/// use est;
+/// use est::locales::nwt_en::Site::JwOrg;
+///
+/// let text: &str = "PROVERBS 17:17";
+/// let expected:String = "[Proverbs 17:17](https://www.jw.org/en/library/bible/study-bible/books/proverbs/17/#v20017017)".to_string();
+/// assert_eq!(expected, est::url_normalized(&JwOrg, text).unwrap());
+/// ```
+pub fn url_normalized<'a, S: Into<Cow<'a, str>>>(site: &Site, text: S) -> Result<String, BibleError> {
+    Ok(parsers::surround::Script::new(text)
+        .normalize()
+        .url(site)?
+        .get_text())
+}
+
+/// Same as [`url`], but the text is treated as a CommonMark document and only scriptures in prose are linked.
+/// Scriptures inside inline code, code blocks, existing links, images, HTML and front matter are left untouched.
+///
+/// ## Example
+/// ```
+/// use est::locales::nwt_en::Site::JwOrg;
+///
+/// let text: &str = "---\ntags: Ge 1:1\n---\nRead Joh 3:16, not `Ge 1:1`.";
+/// let expected:String = "---\ntags: Ge 1:1\n---\nRead [Joh 3:16](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016), not `Ge 1:1`.".to_string();
+/// assert_eq!(expected, est::url_markdown(&JwOrg, text).unwrap());
+/// ```
+pub fn url_markdown<'a, S: Into<Cow<'a, str>>>(site: &Site, text: S) -> Result<String, BibleError> {
+    Ok(parsers::surround::Script::new(text)
+        .markdown()
+        .url(site)?
+        .get_text())
+}
+
+/// Same as [`url`], but each scripture is wrapped in an HTML `<a>` element instead of a Markdown link.
+/// Use [`Script::html`] with an [`Anchor`] to set attributes such as `class`, `target` or `rel`.
+///
+/// ## Example
+/// ```
+/// use est::locales::nwt_en::Site::JwOrg;
+///
+/// let text: &str = "All friends should practice Proverbs 17:17!";
+/// let expected:String = r#"All friends should practice <a href="https://www.jw.org/en/library/bible/study-bible/books/proverbs/17/#v20017017">Proverbs 17:17</a>!"#.to_string();
+/// assert_eq!(expected, est::url_html(&JwOrg, text).unwrap());
+/// ```
+pub fn url_html<'a, S: Into<Cow<'a, str>>>(site: &Site, text: S) -> Result<String, BibleError> {
+    Ok(parsers::surround::Script::new(text)
+        .html(site, &Anchor::new())?
+        .get_text())
+}
+
 /// Returns a vector of the scriptures found in the string passed in.
 /// 
 /// ## Example
@@ -102,7 +208,7 @@ pub fn url<S: Into<String> + Clone>(site: &Site, text: S) -> Result<String, Bibl
 /// let expected:Vec<String> = vec!["John 3:16".to_string()];
 /// assert_eq!(expected, est::get_scriptures(text).unwrap());
 /// ```
-pub fn get_scriptures<S: Into<String> + Clone>(string: S) -> Result<ScriptureCollection, BibleError> {
+pub fn get_scriptures<'a, S: Into<Cow<'a, str>>>(string: S) -> Result<ScriptureCollection, BibleError> {
     parsers::surround::Script::new(string).get_scriptures()
 }
 
@@ -127,13 +233,69 @@ pub fn get_scriptures<S: Into<String> + Clone>(string: S) -> Result<ScriptureCol
 /// let expect = Locations{ slices: vec![], string: text.into() };
 /// assert_eq!(expect, est::get_locations(text));
 /// ```
-pub fn get_locations<'a, S: Into<String> + Clone>(string: S) -> Locations {
+pub fn get_locations<'a, S: Into<Cow<'a, str>>>(string: S) -> Locations {
     parsers::surround::Script::new(string).get_locations()
 }
 
+/// Returns a lazy iterator over the _potential_ scriptures found in the string passed in.
+/// Each [`Match`] borrows from `text` and is only parsed when asked to, which makes this the cheapest way
+/// to scan large amounts of text.
+///
+/// ## Example
+/// ```
+/// use est::locales::nwt_en::Book;
+///
+/// let text: &str = "Read John 3:16, Mary 2:3 and Romans 8:28.";
+/// let books: Vec<Book> = est::references(text)
+///     .filter_map(|m| m.reference().ok())
+///     .map(|reference| reference.book)
+///     .collect();
+/// assert_eq!(books, vec![Book::John, Book::Romans]);
+/// ```
+pub fn references(text: &str) -> References<'_> {
+    References::new(text, Default::default())
+}
+
+/// Same as [`surround`], but reads the text from `reader` and writes the result to `writer`.
+/// The text is processed a few kilobytes at a time, so files of any size can be handled with bounded memory.
+/// Scriptures split between two reads are still found.
+///
+/// ## Example
+/// ```
+/// let text: &[u8] = b"All friends should practice Proverbs 17:17!";
+/// let mut output: Vec<u8> = Vec::new();
+/// est::surround_stream(text, &mut output, "**", "**").unwrap();
+/// assert_eq!(output, b"All friends should practice **Proverbs 17:17**!");
+/// ```
+pub fn surround_stream<R: Read, W: Write>(
+    reader: R,
+    writer: W,
+    prefix: &str,
+    postfix: &str,
+) -> io::Result<()> {
+    parsers::stream::transform(reader, writer, |text| surround(text, prefix, postfix))
+}
+
+/// Same as [`url`], but reads the text from `reader` and writes the result to `writer` with bounded memory.
+/// A [`BibleError`] is returned as an [`io::Error`] of kind `InvalidData`.
+///
+/// ## Example
+/// ```
+/// use est::locales::nwt_en::Site::JwOrg;
+///
+/// let text: &[u8] = b"Read John 3:16.";
+/// let mut output: Vec<u8> = Vec::new();
+/// est::url_stream(&JwOrg, text, &mut output).unwrap();
+/// assert_eq!(output, b"Read [John 3:16](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016).");
+/// ```
+pub fn url_stream<R: Read, W: Write>(site: &Site, reader: R, writer: W) -> io::Result<()> {
+    parsers::stream::transform(reader, writer, |text| url(site, text))
+}
+
 #[cfg(test)]
 mod lib_test {
     use super::*;
+    use crate::locales::nwt_en::BOOKS;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -219,6 +381,54 @@ mod lib_test {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn t_all_caps_url() {
+        let input: &str = "1 CORINTHIANS 13:4-7";
+        let expect: String = "[1 CORINTHIANS 13:4-7](https://www.jw.org/en/library/bible/study-bible/books/1-corinthians/13/#v46013004-v46013007)".to_string();
+        let got: String = url(&Site::JwOrg, input).unwrap();
+        assert_eq!(got, expect)
+    }
+
+    #[test]
+    fn t_surround_normalized_abbr() {
+        let input: &str = "READ JOH 3:16 AND re 12:12 TODAY";
+        let expect: &str = "READ **John 3:16** AND **Revelation 12:12** TODAY";
+        let got = surround_normalized(input, "**", "**").unwrap();
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn t_surround_normalized_invalid_book() {
+        let input: &str = "MARY 3:16 and JOHN 3:16";
+        let expect: &str = "*MARY 3:16* and *John 3:16*";
+        let got = surround_normalized(input, "*", "*").unwrap();
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn t_url_normalized_multi() {
+        let input: &str = "JOHN 3:16, ROMANS 8:28";
+        let expect: String = "[John 3:16](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016), [Romans 8:28](https://www.jw.org/en/library/bible/study-bible/books/romans/8/#v45008028)".to_string();
+        let got: String = url_normalized(&Site::JwOrg, input).unwrap();
+        assert_eq!(got, expect)
+    }
+
+    #[test]
+    fn t_url_normalized_relink() {
+        let once: String = url_normalized(&Site::JwOrg, "Read Ca 2:1.").unwrap();
+        assert!(once.starts_with("Read [Song of Solomon 2:1]("), "{once}");
+        assert_eq!(url(&Site::JwOrg, once.as_str()).unwrap(), once);
+    }
+
+    #[test]
+    fn t_canonical_names_found() {
+        for book in BOOKS {
+            let text: String = format!("Read {} 1:1.", book.canonical_name());
+            assert_eq!(get_scriptures(text.as_str()).unwrap().len(), 1, "{text}");
+            assert!(url(&Site::JwOrg, text.as_str()).is_ok(), "{text}");
+        }
+    }
+
     #[test]
     fn t_get_multiple_scriptures() {
         let input: &str = "A popular scripture is John 3:16. Another is Matthew 24:14.";
@@ -0,0 +1,126 @@
+use aho_corasick::{
+    automaton::OverlappingState, AhoCorasick, AhoCorasickBuilder, Input, Match, MatchKind,
+};
+
+use super::nwt_en::Book;
+
+/// _BookMatcher_ is an automaton compiled from every name and abbreviation of a locale.
+/// It finds the book names in a text in a single pass over it, ignores ASCII case and does not allocate.
+#[derive(Debug)]
+pub(crate) struct BookMatcher {
+    automaton: AhoCorasick,
+    /// The book for each pattern, in the same order as the names the automaton was built from.
+    books: Vec<Book>,
+}
+
+impl BookMatcher {
+    pub(crate) fn new(names: &[(&str, Book)]) -> Self {
+        // Every name found is reported, so the names ending where a chapter starts can all be compared.
+        let automaton: AhoCorasick = AhoCorasickBuilder::new()
+            .ascii_case_insensitive(true)
+            .match_kind(MatchKind::Standard)
+            .build(names.iter().map(|(name, _)| name))
+            .expect("error while compiling the book name automaton");
+
+        Self {
+            automaton,
+            books: names.iter().map(|(_, book)| *book).collect(),
+        }
+    }
+
+    /// Returns the book whose name is exactly `name`.
+    pub(crate) fn lookup(&self, name: &str) -> Option<Book> {
+        self.automaton
+            .find_overlapping_iter(name)
+            .find(|found| found.start() == 0 && found.end() == name.len())
+            .map(|found| self.books[found.pattern().as_usize()])
+    }
+
+    /// Returns the names found in `text`, see [`Names`].
+    pub(crate) fn names<'h>(&'static self, text: &'h str) -> Names<'h> {
+        Names {
+            matcher: self,
+            input: Input::new(text),
+            state: OverlappingState::start(),
+            next: None,
+        }
+    }
+}
+
+/// _Names_ walks the book names of a text from left to right, in the order in which they end, so a scanner
+/// can ask for the names in front of each chapter it finds without going over the text again.
+#[derive(Debug, Clone)]
+pub(crate) struct Names<'h> {
+    matcher: &'static BookMatcher,
+    input: Input<'h>,
+    state: OverlappingState,
+    /// The first name found that ends after the position last asked for.
+    next: Option<Match>,
+}
+
+impl Names<'_> {
+    /// Returns the start of the longest name that ends at `end` and starts where `is_start` allows it.
+    /// Names ending before `end` are skipped for good, so `end` must never go backwards.
+    pub(crate) fn ending_at(&mut self, end: usize, is_start: impl Fn(usize) -> bool) -> Option<usize> {
+        let mut start: Option<usize> = None;
+
+        while let Some(found) = self.next.take().or_else(|| self.advance()) {
+            if found.end() > end {
+                self.next = Some(found);
+                break;
+            }
+            if found.end() == end && is_start(found.start()) {
+                start = Some(start.map_or(found.start(), |start| start.min(found.start())));
+            }
+        }
+
+        start
+    }
+
+    fn advance(&mut self) -> Option<Match> {
+        self.matcher
+            .automaton
+            .find_overlapping(self.input.clone(), &mut self.state);
+        self.state.get_match()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::locales::nwt_en::{MATCHER, NAMES};
+
+    #[test]
+    fn t_lookup() {
+        let matcher: BookMatcher = BookMatcher::new(NAMES);
+        assert_eq!(matcher.lookup("JOHN"), Some(Book::John));
+        assert_eq!(matcher.lookup("1 Jo"), Some(Book::FirstJohn));
+        assert_eq!(matcher.lookup("1 John"), Some(Book::FirstJohn));
+        assert_eq!(matcher.lookup("Song of Solomon"), Some(Book::SongOfSolomon));
+    }
+
+    #[test]
+    fn t_lookup_partial() {
+        let matcher: BookMatcher = BookMatcher::new(NAMES);
+        assert_eq!(matcher.lookup("Johns"), None);
+        assert_eq!(matcher.lookup("Jo"), None);
+        assert_eq!(matcher.lookup(""), None);
+    }
+
+    #[test]
+    fn t_names_ending_at() {
+        let text: &str = "the Song of Solomon 2:1, 1 John 4:8";
+        let mut names: Names = MATCHER.names(text);
+        assert_eq!(names.ending_at(19, |_| true), Some(4));
+        assert_eq!(names.ending_at(31, |start| start != 25), Some(27));
+    }
+
+    #[test]
+    fn t_every_name() {
+        let matcher: BookMatcher = BookMatcher::new(NAMES);
+        for (name, book) in NAMES {
+            assert_eq!(matcher.lookup(name), Some(*book), "{name}");
+            assert_eq!(matcher.lookup(&name.to_uppercase()), Some(*book), "{name}");
+        }
+    }
+}
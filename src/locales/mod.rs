@@ -1,5 +1,9 @@
+pub(crate) mod matcher;
 pub mod nwt_en;
+mod verses;
+use std::borrow::Cow;
 use thiserror::Error;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
 
 #[derive(Debug, Error, PartialEq)]
 pub enum BibleError {
@@ -7,9 +11,41 @@ pub enum BibleError {
     BookNotFound(String),
     #[error("failed to parse scripture reference: '{0}'")]
     ParsingError(String),
+    #[error("the Bible book, {0}, is ambiguous, it could be {books}", books = .1.join(", "))]
+    AmbiguousBook(String, Vec<String>),
+    #[error("{0} has no chapter {1}")]
+    ChapterNotFound(String, u16),
+    #[error("{0} {1} has no verse {2}")]
+    VerseNotFound(String, u16, u16),
+    #[error("the verse range, {0}, ends before it starts")]
+    InvalidRange(String),
 }
 
 pub trait BibleRef {
     fn get_index(book: &str) -> Result<u8, BibleError>;
     fn is_valid(book: &str) -> bool;
 }
+
+/// Removes diacritics from `text` so that names such as `Génesis` and `Genesis` compare as equal.
+/// The text is decomposed (NFD) and all combining marks are dropped; ASCII text is returned as is.
+pub fn fold_diacritics(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(text.nfd().filter(|c| !is_combining_mark(*c)).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn t_fold_diacritics() {
+        assert_eq!(fold_diacritics("Génesis"), "Genesis");
+        assert_eq!(fold_diacritics("Éxodo"), "Exodo");
+        assert_eq!(fold_diacritics("Исаия"), "Исаия");
+        assert_eq!(fold_diacritics("John"), "John");
+    }
+}
@@ -1,7 +1,9 @@
-use super::{BibleError, BibleRef};
+use super::{fold_diacritics, matcher::BookMatcher, verses, BibleError, BibleRef};
 use crate::url::Url;
+use lazy_static::lazy_static;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Book {
     Genesis = 1,
     Exodus,
@@ -71,82 +73,689 @@ pub enum Book {
     Revelation,
 }
 
+/// All books of the Bible in canonical order, `BOOKS[0]` being Genesis.
+pub const BOOKS: [Book; 66] = [
+    Book::Genesis, Book::Exodus, Book::Leviticus, Book::Numbers, Book::Deuteronomy,
+    Book::Joshua, Book::Judges, Book::Ruth, Book::FirstSamuel, Book::SecondSamuel,
+    Book::FirstKings, Book::SecondKings, Book::FirstChronicles, Book::SecondChronicles,
+    Book::Ezra, Book::Nehemiah, Book::Esther, Book::Job, Book::Psalms, Book::Proverbs,
+    Book::Ecclesiastes, Book::SongOfSolomon, Book::Isaiah, Book::Jeremiah, Book::Lamentations,
+    Book::Ezekiel, Book::Daniel, Book::Hosea, Book::Joel, Book::Amos, Book::Obadiah,
+    Book::Jonah, Book::Micah, Book::Nahum, Book::Habakkuk, Book::Zephaniah, Book::Haggai,
+    Book::Zechariah, Book::Malachi, Book::Matthew, Book::Mark, Book::Luke, Book::John,
+    Book::Acts, Book::Romans, Book::FirstCorinthians, Book::SecondCorinthians, Book::Galatians,
+    Book::Ephesians, Book::Philippians, Book::Colossians, Book::FirstThessalonians,
+    Book::SecondThessalonians, Book::FirstTimothy, Book::SecondTimothy, Book::Titus,
+    Book::Philemon, Book::Hebrews, Book::James, Book::FirstPeter, Book::SecondPeter,
+    Book::FirstJohn, Book::SecondJohn, Book::ThirdJohn, Book::Jude, Book::Revelation,
+];
+
+impl Book {
+    /// Looks up a book ignoring both case and diacritics, so `Génesis` resolves to [`Book::Genesis`].
+    pub fn from_folded(book: &str) -> Result<Self, String> {
+        Book::try_from(fold_diacritics(book).as_ref()).map_err(|_| book.to_string())
+    }
+
+    /// Returns every book with a name or abbreviation starting with `prefix`, ignoring case and diacritics.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::locales::nwt_en::Book;
+    ///
+    /// assert_eq!(Book::starting_with("Jud"), vec![Book::Judges, Book::Jude]);
+    /// ```
+    pub fn starting_with(prefix: &str) -> Vec<Self> {
+        let prefix: String = fold_diacritics(prefix.trim()).to_lowercase();
+        let mut books: Vec<Book> = Vec::new();
+        if prefix.is_empty() {
+            return books;
+        }
+
+        for (name, book) in NAMES {
+            if name.starts_with(&prefix) && !books.contains(book) {
+                books.push(*book);
+            }
+        }
+        books.sort();
+        books
+    }
+
+    /// Returns the number of chapters in the book.
+    pub fn chapters(&self) -> u16 {
+        verses::VERSES[*self as usize - 1].len() as u16
+    }
+
+    /// Returns the number of verses in `chapter`, `None` if the book has no such chapter.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::locales::nwt_en::Book;
+    ///
+    /// assert_eq!(Book::John.verses(3), Some(36));
+    /// assert_eq!(Book::Jude.verses(2), None);
+    /// ```
+    pub fn verses(&self, chapter: u16) -> Option<u16> {
+        verses::verses(*self, chapter)
+    }
+
+    /// Returns the name of the book as it appears in the NWT, e.g. `"1 Corinthians"`.
+    pub fn canonical_name(&self) -> &'static str {
+        match self {
+            Book::Genesis => "Genesis",
+            Book::Exodus => "Exodus",
+            Book::Leviticus => "Leviticus",
+            Book::Numbers => "Numbers",
+            Book::Deuteronomy => "Deuteronomy",
+            Book::Joshua => "Joshua",
+            Book::Judges => "Judges",
+            Book::Ruth => "Ruth",
+            Book::FirstSamuel => "1 Samuel",
+            Book::SecondSamuel => "2 Samuel",
+            Book::FirstKings => "1 Kings",
+            Book::SecondKings => "2 Kings",
+            Book::FirstChronicles => "1 Chronicles",
+            Book::SecondChronicles => "2 Chronicles",
+            Book::Ezra => "Ezra",
+            Book::Nehemiah => "Nehemiah",
+            Book::Esther => "Esther",
+            Book::Job => "Job",
+            Book::Psalms => "Psalms",
+            Book::Proverbs => "Proverbs",
+            Book::Ecclesiastes => "Ecclesiastes",
+            Book::SongOfSolomon => "Song of Solomon",
+            Book::Isaiah => "Isaiah",
+            Book::Jeremiah => "Jeremiah",
+            Book::Lamentations => "Lamentations",
+            Book::Ezekiel => "Ezekiel",
+            Book::Daniel => "Daniel",
+            Book::Hosea => "Hosea",
+            Book::Joel => "Joel",
+            Book::Amos => "Amos",
+            Book::Obadiah => "Obadiah",
+            Book::Jonah => "Jonah",
+            Book::Micah => "Micah",
+            Book::Nahum => "Nahum",
+            Book::Habakkuk => "Habakkuk",
+            Book::Zephaniah => "Zephaniah",
+            Book::Haggai => "Haggai",
+            Book::Zechariah => "Zechariah",
+            Book::Malachi => "Malachi",
+            Book::Matthew => "Matthew",
+            Book::Mark => "Mark",
+            Book::Luke => "Luke",
+            Book::John => "John",
+            Book::Acts => "Acts",
+            Book::Romans => "Romans",
+            Book::FirstCorinthians => "1 Corinthians",
+            Book::SecondCorinthians => "2 Corinthians",
+            Book::Galatians => "Galatians",
+            Book::Ephesians => "Ephesians",
+            Book::Philippians => "Philippians",
+            Book::Colossians => "Colossians",
+            Book::FirstThessalonians => "1 Thessalonians",
+            Book::SecondThessalonians => "2 Thessalonians",
+            Book::FirstTimothy => "1 Timothy",
+            Book::SecondTimothy => "2 Timothy",
+            Book::Titus => "Titus",
+            Book::Philemon => "Philemon",
+            Book::Hebrews => "Hebrews",
+            Book::James => "James",
+            Book::FirstPeter => "1 Peter",
+            Book::SecondPeter => "2 Peter",
+            Book::FirstJohn => "1 John",
+            Book::SecondJohn => "2 John",
+            Book::ThirdJohn => "3 John",
+            Book::Jude => "Jude",
+            Book::Revelation => "Revelation",
+        }
+    }
+
+    /// Returns the abbreviation of the book used in the NWT, e.g. `"1Co"`.
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Book::Genesis => "Ge",
+            Book::Exodus => "Ex",
+            Book::Leviticus => "Le",
+            Book::Numbers => "Nu",
+            Book::Deuteronomy => "De",
+            Book::Joshua => "Jos",
+            Book::Judges => "Jg",
+            Book::Ruth => "Ru",
+            Book::FirstSamuel => "1Sa",
+            Book::SecondSamuel => "2Sa",
+            Book::FirstKings => "1Ki",
+            Book::SecondKings => "2Ki",
+            Book::FirstChronicles => "1Ch",
+            Book::SecondChronicles => "2Ch",
+            Book::Ezra => "Ezr",
+            Book::Nehemiah => "Ne",
+            Book::Esther => "Es",
+            Book::Job => "Job",
+            Book::Psalms => "Ps",
+            Book::Proverbs => "Pr",
+            Book::Ecclesiastes => "Ec",
+            Book::SongOfSolomon => "Ca",
+            Book::Isaiah => "Isa",
+            Book::Jeremiah => "Jer",
+            Book::Lamentations => "La",
+            Book::Ezekiel => "Eze",
+            Book::Daniel => "Da",
+            Book::Hosea => "Ho",
+            Book::Joel => "Joe",
+            Book::Amos => "Am",
+            Book::Obadiah => "Ob",
+            Book::Jonah => "Jon",
+            Book::Micah => "Mic",
+            Book::Nahum => "Na",
+            Book::Habakkuk => "Hab",
+            Book::Zephaniah => "Zep",
+            Book::Haggai => "Hag",
+            Book::Zechariah => "Zec",
+            Book::Malachi => "Mal",
+            Book::Matthew => "Mt",
+            Book::Mark => "Mr",
+            Book::Luke => "Lu",
+            Book::John => "Joh",
+            Book::Acts => "Ac",
+            Book::Romans => "Ro",
+            Book::FirstCorinthians => "1Co",
+            Book::SecondCorinthians => "2Co",
+            Book::Galatians => "Ga",
+            Book::Ephesians => "Eph",
+            Book::Philippians => "Php",
+            Book::Colossians => "Col",
+            Book::FirstThessalonians => "1Th",
+            Book::SecondThessalonians => "2Th",
+            Book::FirstTimothy => "1Ti",
+            Book::SecondTimothy => "2Ti",
+            Book::Titus => "Tit",
+            Book::Philemon => "Phm",
+            Book::Hebrews => "Heb",
+            Book::James => "Jas",
+            Book::FirstPeter => "1Pe",
+            Book::SecondPeter => "2Pe",
+            Book::FirstJohn => "1Jo",
+            Book::SecondJohn => "2Jo",
+            Book::ThirdJohn => "3Jo",
+            Book::Jude => "Jude",
+            Book::Revelation => "Re",
+        }
+    }
+
+    /// Looks up a book by its abbreviation in the SBL Handbook of Style, ignoring case and the space after the
+    /// number of a letter.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::locales::nwt_en::Book;
+    ///
+    /// assert_eq!(Book::from_sbl("Rom"), Ok(Book::Romans));
+    /// assert_eq!(Book::from_sbl("1cor"), Ok(Book::FirstCorinthians));
+    /// ```
+    pub fn from_sbl(abbreviation: &str) -> Result<Self, BibleError> {
+        let unspaced = |name: &str| name.chars().filter(|ch| !ch.is_whitespace()).collect::<String>();
+        let wanted: String = unspaced(abbreviation);
+        BOOKS
+            .into_iter()
+            .find(|book| unspaced(book.sbl_abbreviation()).eq_ignore_ascii_case(&wanted))
+            .ok_or_else(|| BibleError::BookNotFound(abbreviation.to_string()))
+    }
+
+    /// Returns the abbreviation of the book in the SBL Handbook of Style, e.g. `"1 Cor"`.
+    pub fn sbl_abbreviation(&self) -> &'static str {
+        match self {
+            Book::Genesis => "Gen",
+            Book::Exodus => "Exod",
+            Book::Leviticus => "Lev",
+            Book::Numbers => "Num",
+            Book::Deuteronomy => "Deut",
+            Book::Joshua => "Josh",
+            Book::Judges => "Judg",
+            Book::Ruth => "Ruth",
+            Book::FirstSamuel => "1 Sam",
+            Book::SecondSamuel => "2 Sam",
+            Book::FirstKings => "1 Kgs",
+            Book::SecondKings => "2 Kgs",
+            Book::FirstChronicles => "1 Chr",
+            Book::SecondChronicles => "2 Chr",
+            Book::Ezra => "Ezra",
+            Book::Nehemiah => "Neh",
+            Book::Esther => "Esth",
+            Book::Job => "Job",
+            Book::Psalms => "Ps",
+            Book::Proverbs => "Prov",
+            Book::Ecclesiastes => "Eccl",
+            Book::SongOfSolomon => "Song",
+            Book::Isaiah => "Isa",
+            Book::Jeremiah => "Jer",
+            Book::Lamentations => "Lam",
+            Book::Ezekiel => "Ezek",
+            Book::Daniel => "Dan",
+            Book::Hosea => "Hos",
+            Book::Joel => "Joel",
+            Book::Amos => "Amos",
+            Book::Obadiah => "Obad",
+            Book::Jonah => "Jonah",
+            Book::Micah => "Mic",
+            Book::Nahum => "Nah",
+            Book::Habakkuk => "Hab",
+            Book::Zephaniah => "Zeph",
+            Book::Haggai => "Hag",
+            Book::Zechariah => "Zech",
+            Book::Malachi => "Mal",
+            Book::Matthew => "Matt",
+            Book::Mark => "Mark",
+            Book::Luke => "Luke",
+            Book::John => "John",
+            Book::Acts => "Acts",
+            Book::Romans => "Rom",
+            Book::FirstCorinthians => "1 Cor",
+            Book::SecondCorinthians => "2 Cor",
+            Book::Galatians => "Gal",
+            Book::Ephesians => "Eph",
+            Book::Philippians => "Phil",
+            Book::Colossians => "Col",
+            Book::FirstThessalonians => "1 Thess",
+            Book::SecondThessalonians => "2 Thess",
+            Book::FirstTimothy => "1 Tim",
+            Book::SecondTimothy => "2 Tim",
+            Book::Titus => "Titus",
+            Book::Philemon => "Phlm",
+            Book::Hebrews => "Heb",
+            Book::James => "Jas",
+            Book::FirstPeter => "1 Pet",
+            Book::SecondPeter => "2 Pet",
+            Book::FirstJohn => "1 John",
+            Book::SecondJohn => "2 John",
+            Book::ThirdJohn => "3 John",
+            Book::Jude => "Jude",
+            Book::Revelation => "Rev",
+        }
+    }
+
+    /// Looks up a book by its OSIS code, which is case sensitive.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::locales::nwt_en::Book;
+    ///
+    /// assert_eq!(Book::from_osis("1Cor"), Ok(Book::FirstCorinthians));
+    /// assert!(Book::from_osis("1Co").is_err());
+    /// ```
+    pub fn from_osis(code: &str) -> Result<Self, BibleError> {
+        BOOKS
+            .into_iter()
+            .find(|book| book.osis() == code)
+            .ok_or_else(|| BibleError::BookNotFound(code.to_string()))
+    }
+
+    /// Returns the OSIS code of the book, e.g. `"1Cor"`.
+    pub fn osis(&self) -> &'static str {
+        match self {
+            Book::Genesis => "Gen",
+            Book::Exodus => "Exod",
+            Book::Leviticus => "Lev",
+            Book::Numbers => "Num",
+            Book::Deuteronomy => "Deut",
+            Book::Joshua => "Josh",
+            Book::Judges => "Judg",
+            Book::Ruth => "Ruth",
+            Book::FirstSamuel => "1Sam",
+            Book::SecondSamuel => "2Sam",
+            Book::FirstKings => "1Kgs",
+            Book::SecondKings => "2Kgs",
+            Book::FirstChronicles => "1Chr",
+            Book::SecondChronicles => "2Chr",
+            Book::Ezra => "Ezra",
+            Book::Nehemiah => "Neh",
+            Book::Esther => "Esth",
+            Book::Job => "Job",
+            Book::Psalms => "Ps",
+            Book::Proverbs => "Prov",
+            Book::Ecclesiastes => "Eccl",
+            Book::SongOfSolomon => "Song",
+            Book::Isaiah => "Isa",
+            Book::Jeremiah => "Jer",
+            Book::Lamentations => "Lam",
+            Book::Ezekiel => "Ezek",
+            Book::Daniel => "Dan",
+            Book::Hosea => "Hos",
+            Book::Joel => "Joel",
+            Book::Amos => "Amos",
+            Book::Obadiah => "Obad",
+            Book::Jonah => "Jonah",
+            Book::Micah => "Mic",
+            Book::Nahum => "Nah",
+            Book::Habakkuk => "Hab",
+            Book::Zephaniah => "Zeph",
+            Book::Haggai => "Hag",
+            Book::Zechariah => "Zech",
+            Book::Malachi => "Mal",
+            Book::Matthew => "Matt",
+            Book::Mark => "Mark",
+            Book::Luke => "Luke",
+            Book::John => "John",
+            Book::Acts => "Acts",
+            Book::Romans => "Rom",
+            Book::FirstCorinthians => "1Cor",
+            Book::SecondCorinthians => "2Cor",
+            Book::Galatians => "Gal",
+            Book::Ephesians => "Eph",
+            Book::Philippians => "Phil",
+            Book::Colossians => "Col",
+            Book::FirstThessalonians => "1Thess",
+            Book::SecondThessalonians => "2Thess",
+            Book::FirstTimothy => "1Tim",
+            Book::SecondTimothy => "2Tim",
+            Book::Titus => "Titus",
+            Book::Philemon => "Phlm",
+            Book::Hebrews => "Heb",
+            Book::James => "Jas",
+            Book::FirstPeter => "1Pet",
+            Book::SecondPeter => "2Pet",
+            Book::FirstJohn => "1John",
+            Book::SecondJohn => "2John",
+            Book::ThirdJohn => "3John",
+            Book::Jude => "Jude",
+            Book::Revelation => "Rev",
+        }
+    }
+
+    /// Looks up a book by its USFM/Paratext code, ignoring case.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::locales::nwt_en::Book;
+    ///
+    /// assert_eq!(Book::from_usfm("JHN"), Ok(Book::John));
+    /// assert_eq!(Book::from_usfm("1co"), Ok(Book::FirstCorinthians));
+    /// ```
+    pub fn from_usfm(code: &str) -> Result<Self, BibleError> {
+        BOOKS
+            .into_iter()
+            .find(|book| book.usfm().eq_ignore_ascii_case(code))
+            .ok_or_else(|| BibleError::BookNotFound(code.to_string()))
+    }
+
+    /// Returns the USFM/Paratext code of the book, e.g. `"1CO"`.
+    pub fn usfm(&self) -> &'static str {
+        match self {
+            Book::Genesis => "GEN",
+            Book::Exodus => "EXO",
+            Book::Leviticus => "LEV",
+            Book::Numbers => "NUM",
+            Book::Deuteronomy => "DEU",
+            Book::Joshua => "JOS",
+            Book::Judges => "JDG",
+            Book::Ruth => "RUT",
+            Book::FirstSamuel => "1SA",
+            Book::SecondSamuel => "2SA",
+            Book::FirstKings => "1KI",
+            Book::SecondKings => "2KI",
+            Book::FirstChronicles => "1CH",
+            Book::SecondChronicles => "2CH",
+            Book::Ezra => "EZR",
+            Book::Nehemiah => "NEH",
+            Book::Esther => "EST",
+            Book::Job => "JOB",
+            Book::Psalms => "PSA",
+            Book::Proverbs => "PRO",
+            Book::Ecclesiastes => "ECC",
+            Book::SongOfSolomon => "SNG",
+            Book::Isaiah => "ISA",
+            Book::Jeremiah => "JER",
+            Book::Lamentations => "LAM",
+            Book::Ezekiel => "EZK",
+            Book::Daniel => "DAN",
+            Book::Hosea => "HOS",
+            Book::Joel => "JOL",
+            Book::Amos => "AMO",
+            Book::Obadiah => "OBA",
+            Book::Jonah => "JON",
+            Book::Micah => "MIC",
+            Book::Nahum => "NAM",
+            Book::Habakkuk => "HAB",
+            Book::Zephaniah => "ZEP",
+            Book::Haggai => "HAG",
+            Book::Zechariah => "ZEC",
+            Book::Malachi => "MAL",
+            Book::Matthew => "MAT",
+            Book::Mark => "MRK",
+            Book::Luke => "LUK",
+            Book::John => "JHN",
+            Book::Acts => "ACT",
+            Book::Romans => "ROM",
+            Book::FirstCorinthians => "1CO",
+            Book::SecondCorinthians => "2CO",
+            Book::Galatians => "GAL",
+            Book::Ephesians => "EPH",
+            Book::Philippians => "PHP",
+            Book::Colossians => "COL",
+            Book::FirstThessalonians => "1TH",
+            Book::SecondThessalonians => "2TH",
+            Book::FirstTimothy => "1TI",
+            Book::SecondTimothy => "2TI",
+            Book::Titus => "TIT",
+            Book::Philemon => "PHM",
+            Book::Hebrews => "HEB",
+            Book::James => "JAS",
+            Book::FirstPeter => "1PE",
+            Book::SecondPeter => "2PE",
+            Book::FirstJohn => "1JN",
+            Book::SecondJohn => "2JN",
+            Book::ThirdJohn => "3JN",
+            Book::Jude => "JUD",
+            Book::Revelation => "REV",
+        }
+    }
+}
+
+/// _Lookup_ holds how book names found in a text are looked up.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Lookup {
+    /// Ignores diacritics, so `Génesis` resolves to [`Book::Genesis`].
+    pub(crate) ignore_diacritics: bool,
+    /// Also accepts the USFM codes of the books, such as `JHN`.
+    pub(crate) usfm: bool,
+    /// Also accepts the SBL abbreviations of the books, such as `Rom`.
+    pub(crate) sbl: bool,
+}
+
+impl Lookup {
+    /// Looks up `name`, returning it as the error when it is not a book.
+    pub(crate) fn book(&self, name: &str) -> Result<Book, String> {
+        let book: Result<Book, String> = match self.ignore_diacritics {
+            true => Book::from_folded(name),
+            false => Book::try_from(name),
+        };
+
+        let book: Result<Book, String> = match self.usfm {
+            true => book.or_else(|name| Book::from_usfm(&name).map_err(|_| name)),
+            false => book,
+        };
+        match self.sbl {
+            true => book.or_else(|name| Book::from_sbl(&name).map_err(|_| name)),
+            false => book,
+        }
+    }
+}
+
+/// Every name and abbreviation of each book as it appears in the NWT, in lowercase.
+pub(crate) const NAMES: &[(&str, Book)] = &[
+    ("genesis", Book::Genesis),
+    ("ge", Book::Genesis),
+    ("gn", Book::Genesis),
+    ("exodus", Book::Exodus),
+    ("ex", Book::Exodus),
+    ("leviticus", Book::Leviticus),
+    ("le", Book::Leviticus),
+    ("numbers", Book::Numbers),
+    ("nu", Book::Numbers),
+    ("deuteronomy", Book::Deuteronomy),
+    ("de", Book::Deuteronomy),
+    ("joshua", Book::Joshua),
+    ("jos", Book::Joshua),
+    ("judges", Book::Judges),
+    ("jg", Book::Judges),
+    ("ruth", Book::Ruth),
+    ("ru", Book::Ruth),
+    ("1 samuel", Book::FirstSamuel),
+    ("1sa", Book::FirstSamuel),
+    ("1 sa", Book::FirstSamuel),
+    ("2 samuel", Book::SecondSamuel),
+    ("2sa", Book::SecondSamuel),
+    ("2 sa", Book::SecondSamuel),
+    ("1 kings", Book::FirstKings),
+    ("1ki", Book::FirstKings),
+    ("1 ki", Book::FirstKings),
+    ("2 kings", Book::SecondKings),
+    ("2ki", Book::SecondKings),
+    ("2 ki", Book::SecondKings),
+    ("1 chronicles", Book::FirstChronicles),
+    ("1ch", Book::FirstChronicles),
+    ("1 ch", Book::FirstChronicles),
+    ("2 chronicles", Book::SecondChronicles),
+    ("2ch", Book::SecondChronicles),
+    ("2 ch", Book::SecondChronicles),
+    ("ezra", Book::Ezra),
+    ("ezr", Book::Ezra),
+    ("nehemiah", Book::Nehemiah),
+    ("ne", Book::Nehemiah),
+    ("esther", Book::Esther),
+    ("es", Book::Esther),
+    ("job", Book::Job),
+    ("psalms", Book::Psalms),
+    ("ps", Book::Psalms),
+    ("proverbs", Book::Proverbs),
+    ("pr", Book::Proverbs),
+    ("ecclesiastes", Book::Ecclesiastes),
+    ("ec", Book::Ecclesiastes),
+    ("song of solomon", Book::SongOfSolomon),
+    ("ca", Book::SongOfSolomon),
+    ("isaiah", Book::Isaiah),
+    ("isa", Book::Isaiah),
+    ("jeremiah", Book::Jeremiah),
+    ("jer", Book::Jeremiah),
+    ("lamentations", Book::Lamentations),
+    ("la", Book::Lamentations),
+    ("ezekiel", Book::Ezekiel),
+    ("eze", Book::Ezekiel),
+    ("daniel", Book::Daniel),
+    ("da", Book::Daniel),
+    ("hosea", Book::Hosea),
+    ("ho", Book::Hosea),
+    ("joel", Book::Joel),
+    ("joe", Book::Joel),
+    ("amos", Book::Amos),
+    ("am", Book::Amos),
+    ("obadiah", Book::Obadiah),
+    ("ob", Book::Obadiah),
+    ("jonah", Book::Jonah),
+    ("jon", Book::Jonah),
+    ("micah", Book::Micah),
+    ("mic", Book::Micah),
+    ("nahum", Book::Nahum),
+    ("na", Book::Nahum),
+    ("habakkuk", Book::Habakkuk),
+    ("hab", Book::Habakkuk),
+    ("zephaniah", Book::Zephaniah),
+    ("zep", Book::Zephaniah),
+    ("haggai", Book::Haggai),
+    ("hag", Book::Haggai),
+    ("zechariah", Book::Zechariah),
+    ("zec", Book::Zechariah),
+    ("malachi", Book::Malachi),
+    ("mal", Book::Malachi),
+    ("matthew", Book::Matthew),
+    ("mt", Book::Matthew),
+    ("mark", Book::Mark),
+    ("mr", Book::Mark),
+    ("luke", Book::Luke),
+    ("lu", Book::Luke),
+    ("john", Book::John),
+    ("joh", Book::John),
+    ("acts", Book::Acts),
+    ("ac", Book::Acts),
+    ("romans", Book::Romans),
+    ("ro", Book::Romans),
+    ("1 corinthians", Book::FirstCorinthians),
+    ("1co", Book::FirstCorinthians),
+    ("1 co", Book::FirstCorinthians),
+    ("2 corinthians", Book::SecondCorinthians),
+    ("2co", Book::SecondCorinthians),
+    ("2 co", Book::SecondCorinthians),
+    ("galatians", Book::Galatians),
+    ("ga", Book::Galatians),
+    ("ephesians", Book::Ephesians),
+    ("eph", Book::Ephesians),
+    ("philippians", Book::Philippians),
+    ("php", Book::Philippians),
+    ("colossians", Book::Colossians),
+    ("col", Book::Colossians),
+    ("1 thessalonians", Book::FirstThessalonians),
+    ("1th", Book::FirstThessalonians),
+    ("1 th", Book::FirstThessalonians),
+    ("2 thessalonians", Book::SecondThessalonians),
+    ("2th", Book::SecondThessalonians),
+    ("2 th", Book::SecondThessalonians),
+    ("1 timothy", Book::FirstTimothy),
+    ("1ti", Book::FirstTimothy),
+    ("1 ti", Book::FirstTimothy),
+    ("2 timothy", Book::SecondTimothy),
+    ("2ti", Book::SecondTimothy),
+    ("2 ti", Book::SecondTimothy),
+    ("titus", Book::Titus),
+    ("tit", Book::Titus),
+    ("philemon", Book::Philemon),
+    ("phm", Book::Philemon),
+    ("hebrews", Book::Hebrews),
+    ("heb", Book::Hebrews),
+    ("james", Book::James),
+    ("jas", Book::James),
+    ("1 peter", Book::FirstPeter),
+    ("1pe", Book::FirstPeter),
+    ("1 pe", Book::FirstPeter),
+    ("2 peter", Book::SecondPeter),
+    ("2pe", Book::SecondPeter),
+    ("2 pe", Book::SecondPeter),
+    ("1 john", Book::FirstJohn),
+    ("1jo", Book::FirstJohn),
+    ("1 jo", Book::FirstJohn),
+    ("2 john", Book::SecondJohn),
+    ("2jo", Book::SecondJohn),
+    ("2 jo", Book::SecondJohn),
+    ("3 john", Book::ThirdJohn),
+    ("3jo", Book::ThirdJohn),
+    ("3 jo", Book::ThirdJohn),
+    ("jude", Book::Jude),
+    ("revelation", Book::Revelation),
+    ("re", Book::Revelation),
+];
+
+lazy_static! {
+    /// The compiled matcher for all the names in [`NAMES`].
+    pub(crate) static ref MATCHER: BookMatcher = BookMatcher::new(NAMES);
+}
+
 impl TryFrom<&str> for Book {
     type Error = String;
 
-    // Naming as it appeares on the NWT.
+    /// Looks up a book by any of its names or abbreviations, ignoring case.
     fn try_from(book: &str) -> Result<Self, Self::Error> {
-        match book.to_lowercase().as_str() {
-            "genesis" | "gn" => Ok(Book::Genesis),
-            "exodus" | "ex" => Ok(Book::Exodus),
-            "leviticus" | "le" => Ok(Book::Leviticus),
-            "numbers" | "nu" => Ok(Book::Numbers),
-            "deuteronomy" | "de" => Ok(Book::Deuteronomy),
-            "joshua" | "jos" => Ok(Book::Joshua),
-            "judges" | "jg" => Ok(Book::Judges),
-            "ruth" | "ru" => Ok(Book::Ruth),
-            "1 samuel" | "1sa" | "1 sa" => Ok(Book::FirstSamuel),
-            "2 samuel" | "2sa" | "2 sa" => Ok(Book::SecondSamuel),
-            "1 kings" | "1ki" | "1 ki" => Ok(Book::FirstKings),
-            "2 kings" | "2ki" | "2 ki" => Ok(Book::SecondKings),
-            "1 chronicles" | "1ch" | "1 ch" => Ok(Book::FirstChronicles),
-            "2 chronicles" | "2ch" | "2 ch" => Ok(Book::SecondChronicles),
-            "ezra" | "ezr" => Ok(Book::Ezra),
-            "nehemiah" | "ne" => Ok(Book::Nehemiah),
-            "esther" | "es" => Ok(Book::Esther),
-            "job" => Ok(Book::Job),
-            "psalms" | "ps" => Ok(Book::Psalms),
-            "proverbs" | "pr" => Ok(Book::Proverbs),
-            "ecclesiastes" | "ec" => Ok(Book::Ecclesiastes),
-            "song of solomon" | "ca" => Ok(Book::SongOfSolomon),
-            "isaiah" | "isa" => Ok(Book::Isaiah),
-            "jeremiah" | "jer" => Ok(Book::Jeremiah),
-            "lamentations" | "la" => Ok(Book::Lamentations),
-            "ezekiel" | "eze" => Ok(Book::Ezekiel),
-            "daniel" | "da" => Ok(Book::Daniel),
-            "hosea" | "ho" => Ok(Book::Hosea),
-            "joel" | "joe" => Ok(Book::Joel),
-            "amos" | "am" => Ok(Book::Amos),
-            "obadiah" | "ob" => Ok(Book::Obadiah),
-            "jonah" | "jon" => Ok(Book::Jonah),
-            "micah" | "mic" => Ok(Book::Micah),
-            "nahum" | "na" => Ok(Book::Nahum),
-            "habakkuk" | "hab" => Ok(Book::Habakkuk),
-            "zephaniah" | "zep" => Ok(Book::Zephaniah),
-            "haggai" | "hag" => Ok(Book::Haggai),
-            "zechariah" | "zec" => Ok(Book::Zechariah),
-            "malachi" | "mal" => Ok(Book::Malachi),
-            "matthew" | "mt" => Ok(Book::Matthew),
-            "mark" | "mr" => Ok(Book::Mark),
-            "luke" | "lu" => Ok(Book::Luke),
-            "john" | "joh" => Ok(Book::John),
-            "acts" | "ac" => Ok(Book::Acts),
-            "romans" | "ro" => Ok(Book::Romans),
-            "1 corinthians" | "1co" | "1 co" => Ok(Book::FirstCorinthians),
-            "2 corinthians" | "2co" | "2 co" => Ok(Book::SecondCorinthians),
-            "galatians" | "ga" => Ok(Book::Galatians),
-            "ephesians" | "eph" => Ok(Book::Ephesians),
-            "philippians" | "php" => Ok(Book::Philippians),
-            "colossians" | "col" => Ok(Book::Colossians),
-            "1 thessalonians" | "1th" | "1 th" => Ok(Book::FirstThessalonians),
-            "2 thessalonians" | "2th" | "2 th" => Ok(Book::SecondThessalonians),
-            "1 timothy" | "1ti" | "1 ti" => Ok(Book::FirstTimothy),
-            "2 timothy" | "2ti" | "2 ti" => Ok(Book::SecondTimothy),
-            "titus" | "tit" => Ok(Book::Titus),
-            "philemon" | "phm" => Ok(Book::Philemon),
-            "hebrews" | "heb" => Ok(Book::Hebrews),
-            "james" | "jas" => Ok(Book::James),
-            "1 peter" | "1pe" | "1 pe" => Ok(Book::FirstPeter),
-            "2 peter" | "2pe" | "2 pe" => Ok(Book::SecondPeter),
-            "1 john" | "1jo" | "1 jo" => Ok(Book::FirstJohn),
-            "2 john" | "2jo" | "2 jo" => Ok(Book::SecondJohn),
-            "3 john" | "3jo" | "3 jo" => Ok(Book::ThirdJohn),
-            "jude" => Ok(Book::Jude),
-            "revelation" | "re" => Ok(Book::Revelation),
-
-            // Return the string containing the non-found scripture as an error.
-            _ => Err(book.to_string()),
-        }
+        // Return the string containing the non-found scripture as an error.
+        MATCHER.lookup(book).ok_or_else(|| book.to_string())
+    }
+}
+
+impl TryFrom<u8> for Book {
+    type Error = BibleError;
+
+    /// Converts a book number, `1` for Genesis through `66` for Revelation, into a `Book`.
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        BOOKS
+            .get(usize::from(index).wrapping_sub(1))
+            .copied()
+            .ok_or_else(|| BibleError::BookNotFound(index.to_string()))
     }
 }
 
@@ -161,12 +770,12 @@ impl From<Book> for &str {
             Book::Joshua => "joshua",
             Book::Judges => "judges",
             Book::Ruth => "ruth",
-            Book::FirstSamuel => "1 samuel",
-            Book::SecondSamuel => "2 samuel",
-            Book::FirstKings => "1 kings",
-            Book::SecondKings => "2 kings",
-            Book::FirstChronicles => "1 chronicles",
-            Book::SecondChronicles => "2 chronicles",
+            Book::FirstSamuel => "1-samuel",
+            Book::SecondSamuel => "2-samuel",
+            Book::FirstKings => "1-kings",
+            Book::SecondKings => "2-kings",
+            Book::FirstChronicles => "1-chronicles",
+            Book::SecondChronicles => "2-chronicles",
             Book::Ezra => "ezra",
             Book::Nehemiah => "nehemiah",
             Book::Esther => "esther",
@@ -174,7 +783,7 @@ impl From<Book> for &str {
             Book::Psalms => "psalms",
             Book::Proverbs => "proverbs",
             Book::Ecclesiastes => "ecclesiastes",
-            Book::SongOfSolomon => "song of solomon",
+            Book::SongOfSolomon => "song-of-solomon",
             Book::Isaiah => "isaiah",
             Book::Jeremiah => "jeremiah",
             Book::Lamentations => "lamentations",
@@ -198,25 +807,25 @@ impl From<Book> for &str {
             Book::John => "john",
             Book::Acts => "acts",
             Book::Romans => "romans",
-            Book::FirstCorinthians => "1 corinthians",
-            Book::SecondCorinthians => "2 corinthians",
+            Book::FirstCorinthians => "1-corinthians",
+            Book::SecondCorinthians => "2-corinthians",
             Book::Galatians => "galatians",
             Book::Ephesians => "ephesians",
             Book::Philippians => "philippians",
             Book::Colossians => "colossians",
-            Book::FirstThessalonians => "1 thessalonians",
-            Book::SecondThessalonians => "2 thessalonians",
-            Book::FirstTimothy => "1 timothy",
-            Book::SecondTimothy => "2 timothy",
+            Book::FirstThessalonians => "1-thessalonians",
+            Book::SecondThessalonians => "2-thessalonians",
+            Book::FirstTimothy => "1-timothy",
+            Book::SecondTimothy => "2-timothy",
             Book::Titus => "titus",
             Book::Philemon => "philemon",
             Book::Hebrews => "hebrews",
             Book::James => "james",
-            Book::FirstPeter => "1 peter",
-            Book::SecondPeter => "2 peter",
-            Book::FirstJohn => "1 john",
-            Book::SecondJohn => "2 john",
-            Book::ThirdJohn => "3 john",
+            Book::FirstPeter => "1-peter",
+            Book::SecondPeter => "2-peter",
+            Book::FirstJohn => "1-john",
+            Book::SecondJohn => "2-john",
+            Book::ThirdJohn => "3-john",
             Book::Jude => "jude",
             Book::Revelation => "revelation",
         }
@@ -241,10 +850,32 @@ impl BibleRef for Book {
 }
 
 /// All websites supported for the en_us language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Site {
     JwOrg,
 }
 
+impl Site {
+    /// Every supported website.
+    pub const ALL: [Site; 1] = [Site::JwOrg];
+
+    /// True if `url` points to a scripture on this site, such as a link built by `est::url`.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::locales::nwt_en::Site;
+    ///
+    /// assert!(Site::JwOrg.is_link("https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016"));
+    /// assert!(!Site::JwOrg.is_link("https://example.com/john/3"));
+    /// ```
+    pub fn is_link(&self, url: &str) -> bool {
+        let template: String = self.get_single();
+        let base: &str = template.split('{').next().unwrap_or_default();
+        url.starts_with(base)
+    }
+}
+
 /// The UrlTemplate holds the URL structure for single and ranged verse scriptures.
 pub(crate) struct UrlTemplate {
     single: String,
@@ -297,6 +928,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::unnecessary_cast)]
     fn test_matthew_index() {
         let expect: u8 = 40;
         let result: u8 = Book::get_index("Matthew").unwrap();
@@ -304,12 +936,36 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::unnecessary_cast)]
     fn test_john_abbr() {
         let expect: u8 = 43;
         let result: u8 = Book::get_index("joh").unwrap();
         assert_eq!(result as u8, expect);
     }
 
+    #[test]
+    fn test_abbreviation_round_trip() {
+        for book in BOOKS {
+            assert_eq!(Book::try_from(book.abbreviation()), Ok(book));
+        }
+    }
+
+    #[test]
+    fn test_codes_round_trip() {
+        for book in BOOKS {
+            assert_eq!(Book::from_osis(book.osis()), Ok(book));
+            assert_eq!(Book::from_usfm(book.usfm()), Ok(book));
+            assert_eq!(Book::from_sbl(book.sbl_abbreviation()), Ok(book));
+        }
+    }
+
+    #[test]
+    fn test_url_slug() {
+        assert_eq!(<&str>::from(Book::FirstCorinthians), "1-corinthians");
+        assert_eq!(<&str>::from(Book::SongOfSolomon), "song-of-solomon");
+        assert!(BOOKS.iter().all(|book| !<&str>::from(*book).contains(' ')));
+    }
+
     #[test]
     #[should_panic = "error"]
     fn test_error() {
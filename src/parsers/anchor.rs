@@ -0,0 +1,125 @@
+/// _Anchor_ describes the HTML `<a>` element built around each scripture by [`crate::Script::html`].
+///
+/// Every value is escaped when the element is built, so it is safe to pass untrusted text.
+///
+/// ## Example
+/// ```
+/// use est::{Anchor, Script};
+/// use est::locales::nwt_en::Site;
+///
+/// let anchor: Anchor = Anchor::new().class("scripture").target("_blank").data_ref();
+/// let got: String = Script::new("Read John 3:16.").html(&Site::JwOrg, &anchor).unwrap().get_text();
+/// let expect: &str = r#"Read <a href="https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016" class="scripture" target="_blank" rel="noopener noreferrer" data-ref="John 3:16">John 3:16</a>."#;
+/// assert_eq!(expect, got);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Anchor<'a> {
+    class: Option<&'a str>,
+    target: Option<&'a str>,
+    rel: Option<&'a str>,
+    /// When `true`, the canonical form of the scripture is added as `data-ref`.
+    data_ref: bool,
+    /// Any other attributes, added in order after the ones above.
+    attributes: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Anchor<'a> {
+    /// An anchor with only the `href` attribute.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The `class` attribute.
+    pub fn class(mut self, class: &'a str) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    /// The `target` attribute. When the target is `_blank` and no `rel` is set, `rel="noopener noreferrer"` is added.
+    pub fn target(mut self, target: &'a str) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// The `rel` attribute.
+    pub fn rel(mut self, rel: &'a str) -> Self {
+        self.rel = Some(rel);
+        self
+    }
+
+    /// Adds the scripture with the canonical book name as the `data-ref` attribute, i.e. `JOH 3:16` gives `John 3:16`.
+    pub fn data_ref(mut self) -> Self {
+        self.data_ref = true;
+        self
+    }
+
+    /// Adds any other attribute, such as `("title", "Read online")`.
+    pub fn attribute(mut self, name: &'a str, value: &'a str) -> Self {
+        self.attributes.push((name, value));
+        self
+    }
+
+    /// Pushes the opening tag of the anchor onto `output`, `data_ref` is the canonical form of the scripture.
+    pub(crate) fn push_open(&self, output: &mut String, href: &str, data_ref: &str) {
+        let rel: Option<&str> = match (self.rel, self.target) {
+            (None, Some("_blank")) => Some("noopener noreferrer"),
+            (rel, _) => rel,
+        };
+
+        output.push_str("<a");
+        push_attribute(output, "href", href);
+        let attributes = [("class", self.class), ("target", self.target), ("rel", rel)];
+        for (name, value) in attributes {
+            if let Some(value) = value {
+                push_attribute(output, name, value);
+            }
+        }
+        if self.data_ref {
+            push_attribute(output, "data-ref", data_ref);
+        }
+        for (name, value) in self.attributes.iter() {
+            push_attribute(output, name, value);
+        }
+        output.push('>');
+    }
+}
+
+fn push_attribute(output: &mut String, name: &str, value: &str) {
+    output.push(' ');
+    output.push_str(name);
+    output.push_str("=\"");
+    escape(output, value);
+    output.push('"');
+}
+
+/// Pushes `text` onto `output` with the characters that are special in HTML escaped.
+pub(crate) fn escape(output: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            _ => output.push(ch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn t_escape_attributes() {
+        let anchor: Anchor = Anchor::new()
+            .rel("nofollow")
+            .target("_blank")
+            .attribute("title", "\"Love\" & <peace>");
+        let mut got: String = String::new();
+        anchor.push_open(&mut got, "https://example.com/?a=1&b='2'", "John 3:16");
+        let expect: &str = r#"<a href="https://example.com/?a=1&amp;b=&#39;2&#39;" target="_blank" rel="nofollow" title="&quot;Love&quot; &amp; &lt;peace&gt;">"#;
+        assert_eq!(got, expect);
+    }
+}
@@ -0,0 +1,185 @@
+use std::ops::RangeInclusive;
+
+use crate::locales::{nwt_en::Book, BibleError};
+
+use super::reference::{Reference, VerseRange};
+
+/// The place of the book in a BCV number, `BBCCCVVV`.
+pub(crate) const BOOK: u32 = 1_000_000;
+/// The place of the chapter in a BCV number, `BBCCCVVV`.
+pub(crate) const CHAPTER: u32 = 1_000;
+/// The highest chapter or verse that fits in a BCV number.
+const MAX: u16 = 999;
+
+impl Reference {
+    /// Returns the first verse of the reference as a BCV number, `BBCCCVVV`, the verse id used in jw.org links.
+    /// John 3:16 is `43003016`. The verse is `000` when the reference has no verse, so a chapter comes before
+    /// its verses. BCV numbers sort in the order of the Bible, which makes them a compact key for a database.
+    ///
+    /// Chapters and verses above 999 do not fit, nor does chapter 0, and give a `ChapterNotFound` or
+    /// `VerseNotFound` error rather than the number of another verse.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::Reference;
+    /// use est::locales::BibleError;
+    ///
+    /// assert_eq!(Reference::parse("Genesis 1:1").unwrap().to_bcv(), Ok(1_001_001));
+    /// assert_eq!(Reference::parse("John 3:16-18").unwrap().to_bcv(), Ok(43_003_016));
+    /// assert_eq!(Reference::parse("Psalms 23:").unwrap().to_bcv(), Ok(19_023_000));
+    /// assert_eq!(
+    ///     Reference::parse("John 1000:1").unwrap().to_bcv(),
+    ///     Err(BibleError::ChapterNotFound("John".to_string(), 1000))
+    /// );
+    /// ```
+    pub fn to_bcv(&self) -> Result<u32, BibleError> {
+        let verse: u16 = self.verses.first().map_or(0, |verses| verses.start);
+        bcv(self.book, self.chapter, verse)
+    }
+
+    /// Returns the BCV numbers of the verses, one inclusive range per [`VerseRange`], to test whether a verse
+    /// is referenced or whether two references overlap. A reference without verses covers the whole chapter.
+    /// Fails like [`Reference::to_bcv`] when a chapter or verse does not fit.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::Reference;
+    ///
+    /// let reference: Reference = Reference::parse("1 corinthians 13:4-7,13").unwrap();
+    /// let ranges = reference.to_bcv_ranges().unwrap();
+    /// assert_eq!(ranges, vec![46_013_004..=46_013_007, 46_013_013..=46_013_013]);
+    /// assert!(ranges.iter().any(|verses| verses.contains(&46_013_005)));
+    /// ```
+    pub fn to_bcv_ranges(&self) -> Result<Vec<RangeInclusive<u32>>, BibleError> {
+        if self.verses.is_empty() {
+            return Ok(vec![
+                bcv(self.book, self.chapter, 0)?..=bcv(self.book, self.chapter, MAX)?,
+            ]);
+        }
+
+        self.verses
+            .iter()
+            .map(|verses| {
+                Ok(bcv(self.book, self.chapter, verses.start)?
+                    ..=bcv(self.book, self.chapter, verses.end)?)
+            })
+            .collect()
+    }
+
+    /// Decodes a BCV number, `BBCCCVVV`, into a reference to a single verse, or to a chapter when the verse is
+    /// `000`. The reference is not validated, see [`Reference::validate`].
+    ///
+    /// ## Example
+    /// ```
+    /// use est::{Reference, VerseRange};
+    /// use est::locales::nwt_en::Book;
+    ///
+    /// let got: Reference = Reference::from_bcv(43_003_016).unwrap();
+    /// assert_eq!(got, Reference { book: Book::John, chapter: 3, verses: vec![VerseRange { start: 16, end: 16 }] });
+    /// assert_eq!(Reference::from_bcv(got.to_bcv().unwrap()), Ok(got));
+    /// ```
+    pub fn from_bcv(bcv: u32) -> Result<Reference, BibleError> {
+        let book: u8 =
+            u8::try_from(bcv / BOOK).map_err(|_| BibleError::ParsingError(bcv.to_string()))?;
+        let chapter: u16 = (bcv / CHAPTER % CHAPTER) as u16;
+        let verse: u16 = (bcv % CHAPTER) as u16;
+        if chapter == 0 {
+            return Err(BibleError::ParsingError(bcv.to_string()));
+        }
+
+        Ok(Reference {
+            book: Book::try_from(book)?,
+            chapter,
+            verses: match verse {
+                0 => Vec::new(),
+                verse => vec![VerseRange {
+                    start: verse,
+                    end: verse,
+                }],
+            },
+        })
+    }
+}
+
+/// Encodes `book`, `chapter` and `verse` as `BBCCCVVV`, an error when the chapter or verse does not fit.
+pub(crate) fn bcv(book: Book, chapter: u16, verse: u16) -> Result<u32, BibleError> {
+    if chapter == 0 || chapter > MAX {
+        return Err(BibleError::ChapterNotFound(
+            book.canonical_name().to_string(),
+            chapter,
+        ));
+    }
+    if verse > MAX {
+        return Err(BibleError::VerseNotFound(
+            book.canonical_name().to_string(),
+            chapter,
+            verse,
+        ));
+    }
+
+    Ok(book as u32 * BOOK + u32::from(chapter) * CHAPTER + u32::from(verse))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn t_bcv_order() {
+        let mut references: Vec<Reference> =
+            ["Revelation 22:21", "Genesis 1:1", "John 3:", "John 3:16"]
+                .iter()
+                .map(|scripture| Reference::parse(scripture).unwrap())
+                .collect();
+        references.sort_by_key(|reference| reference.to_bcv().unwrap());
+        let got: Vec<u32> = references
+            .iter()
+            .map(|reference| reference.to_bcv().unwrap())
+            .collect();
+        assert_eq!(got, vec![1_001_001, 43_003_000, 43_003_016, 66_022_021]);
+    }
+
+    #[test]
+    fn t_bcv_ranges() {
+        let got = Reference::parse("Psalms 23:").unwrap().to_bcv_ranges();
+        assert_eq!(got, Ok(vec![19_023_000..=19_023_999]));
+    }
+
+    #[test]
+    fn t_to_bcv_errors() {
+        let encode = |scripture: &str| Reference::parse(scripture).unwrap().to_bcv();
+        assert_eq!(
+            encode("John 9999:1"),
+            Err(BibleError::ChapterNotFound("John".to_string(), 9999))
+        );
+        assert_eq!(
+            encode("John 3:1000"),
+            Err(BibleError::VerseNotFound("John".to_string(), 3, 1000))
+        );
+        assert_eq!(encode("John 999:999"), Ok(43_999_999));
+        let ranges = Reference::parse("John 3:16-1000").unwrap().to_bcv_ranges();
+        assert_eq!(
+            ranges,
+            Err(BibleError::VerseNotFound("John".to_string(), 3, 1000))
+        );
+    }
+
+    #[test]
+    fn t_from_bcv_errors() {
+        let parse = |bcv: u32| Reference::from_bcv(bcv).unwrap_err();
+        assert_eq!(
+            parse(67_001_001),
+            BibleError::BookNotFound("67".to_string())
+        );
+        assert_eq!(parse(1_001), BibleError::BookNotFound("0".to_string()));
+        assert_eq!(
+            parse(43_000_016),
+            BibleError::ParsingError("43000016".to_string())
+        );
+        assert_eq!(
+            parse(u32::MAX),
+            BibleError::ParsingError(u32::MAX.to_string())
+        );
+    }
+}
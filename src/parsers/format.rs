@@ -0,0 +1,196 @@
+use std::borrow::Cow;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// _LinkFormat_ is the markup [`crate::Script::url`] uses to link each scripture.
+/// Links already written in the format, or in Markdown, are recognized, so linking the output again does not nest
+/// links.
+///
+/// ## Example
+/// ```
+/// use est::{LinkFormat, Script};
+/// use est::locales::nwt_en::Site;
+///
+/// let got: String = Script::new("Read John 3:16.")
+///     .link_format(LinkFormat::Org)
+///     .url(&Site::JwOrg)
+///     .unwrap()
+///     .get_text();
+/// assert_eq!("Read [[https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016][John 3:16]].", got);
+/// ```
+pub enum LinkFormat {
+    /// `[John 3:16](url)`
+    #[default]
+    Markdown,
+    /// `link:url[John 3:16]`
+    AsciiDoc,
+    /// `` `John 3:16 <url>`_ ``
+    RestructuredText,
+    /// `[[url][John 3:16]]`
+    Org,
+    /// `\href{url}{John 3:16}`, with the characters that are special to LaTeX escaped.
+    Latex,
+    /// `[url=url]John 3:16[/url]`
+    BBCode,
+}
+
+impl LinkFormat {
+    /// Pushes a link to `url` with `text` onto `output`.
+    pub(crate) fn push(&self, output: &mut String, url: &str, text: &str) {
+        match self {
+            LinkFormat::Markdown => {
+                output.push('[');
+                output.push_str(text);
+                output.push_str("](");
+                output.push_str(url);
+                output.push(')');
+            }
+            LinkFormat::AsciiDoc => {
+                output.push_str("link:");
+                output.push_str(url);
+                output.push('[');
+                output.push_str(text);
+                output.push(']');
+            }
+            LinkFormat::RestructuredText => {
+                output.push('`');
+                output.push_str(text);
+                output.push_str(" <");
+                output.push_str(url);
+                output.push_str(">`_");
+            }
+            LinkFormat::Org => {
+                output.push_str("[[");
+                output.push_str(url);
+                output.push_str("][");
+                output.push_str(text);
+                output.push_str("]]");
+            }
+            LinkFormat::Latex => {
+                output.push_str("\\href{");
+                latex_url(output, url);
+                output.push_str("}{");
+                latex_text(output, text);
+                output.push('}');
+            }
+            LinkFormat::BBCode => {
+                output.push_str("[url=");
+                output.push_str(url);
+                output.push(']');
+                output.push_str(text);
+                output.push_str("[/url]");
+            }
+        }
+    }
+
+    /// Pushes `url` onto `output` as the target of a link, escaped as [`LinkFormat::push`] does.
+    pub(crate) fn push_url(&self, output: &mut String, url: &str) {
+        match self {
+            LinkFormat::Latex => latex_url(output, url),
+            _ => output.push_str(url),
+        }
+    }
+
+    /// Returns the URL written as the `target` of a link, without the escaping added by [`LinkFormat::push`].
+    pub(crate) fn url<'t>(&self, target: &'t str) -> Cow<'t, str> {
+        match self {
+            LinkFormat::Latex if target.contains('\\') => {
+                let mut url: String = String::with_capacity(target.len());
+                let mut chars = target.chars();
+                while let Some(ch) = chars.next() {
+                    match ch {
+                        '\\' => url.extend(chars.next()),
+                        _ => url.push(ch),
+                    }
+                }
+                Cow::Owned(url)
+            }
+            _ => Cow::Borrowed(target),
+        }
+    }
+}
+
+/// Escapes the characters `\href` does not accept as is in a URL.
+fn latex_url(output: &mut String, url: &str) {
+    for ch in url.chars() {
+        if matches!(ch, '#' | '%' | '\\' | '{' | '}') {
+            output.push('\\');
+        }
+        output.push(ch);
+    }
+}
+
+/// Escapes the characters that are special to LaTeX in text.
+fn latex_text(output: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                output.push('\\');
+                output.push(ch);
+            }
+            '~' => output.push_str("\\textasciitilde{}"),
+            '^' => output.push_str("\\textasciicircum{}"),
+            '\\' => output.push_str("\\textbackslash{}"),
+            _ => output.push(ch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn link(format: LinkFormat) -> String {
+        let mut output: String = String::new();
+        format.push(&mut output, "https://example.com/a%20b#v1", "John 3:16");
+        output
+    }
+
+    #[test]
+    fn t_every_format() {
+        assert_eq!(
+            link(LinkFormat::Markdown),
+            "[John 3:16](https://example.com/a%20b#v1)"
+        );
+        assert_eq!(
+            link(LinkFormat::AsciiDoc),
+            "link:https://example.com/a%20b#v1[John 3:16]"
+        );
+        assert_eq!(
+            link(LinkFormat::RestructuredText),
+            "`John 3:16 <https://example.com/a%20b#v1>`_"
+        );
+        assert_eq!(
+            link(LinkFormat::Org),
+            "[[https://example.com/a%20b#v1][John 3:16]]"
+        );
+        assert_eq!(
+            link(LinkFormat::Latex),
+            r"\href{https://example.com/a\%20b\#v1}{John 3:16}"
+        );
+        assert_eq!(
+            link(LinkFormat::BBCode),
+            "[url=https://example.com/a%20b#v1]John 3:16[/url]"
+        );
+    }
+
+    #[test]
+    fn t_url_round_trip() {
+        let url: &str = "https://example.com/a%20b#v1";
+        for format in [LinkFormat::Markdown, LinkFormat::Latex] {
+            let mut target: String = String::new();
+            format.push_url(&mut target, url);
+            assert_eq!(format.url(&target), url);
+        }
+    }
+
+    #[test]
+    fn t_latex_text() {
+        let mut output: String = String::new();
+        latex_text(&mut output, r"50% & $5_a {b} ~^\");
+        assert_eq!(
+            output,
+            r"50\% \& \$5\_a \{b\} \textasciitilde{}\textasciicircum{}\textbackslash{}"
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Finds the text nodes of an HTML document, so scriptures inside tags, attributes, comments, scripts, styles,
+//! existing links and code can be left untouched.
+//!
+//! This is not a full HTML parser, it only needs to tell markup from text. The document is never rewritten,
+//! entities and everything outside of the text nodes are kept byte for byte.
+use super::surround::ScriptSlice;
+
+/// Elements whose content is not parsed as HTML, it runs up to the matching closing tag.
+const RAW_TEXT: [&str; 4] = ["script", "style", "textarea", "title"];
+/// Elements whose text is left alone, including the text of any element nested inside them.
+const SKIPPED: [&str; 3] = ["a", "code", "pre"];
+
+/// Returns the byte ranges of the text nodes in an HTML document that scriptures may be found in, in order.
+pub(crate) fn text_nodes(text: &str) -> Vec<ScriptSlice> {
+    let mut ranges: Vec<ScriptSlice> = Vec::new();
+    // The skipped element the tokenizer is inside of and how deeply it is nested.
+    let mut skipped: Option<(&str, usize)> = None;
+    let mut text_start: usize = 0;
+    let mut pos: usize = 0;
+
+    while let Some(offset) = text[pos..].find('<') {
+        let lt: usize = pos + offset;
+        let rest: &str = &text[lt..];
+
+        let tag: Option<Tag> = Tag::parse(rest);
+        let end: usize = if rest.starts_with("<!--") {
+            lt + find_end(rest, "-->")
+        } else if rest.starts_with("<![CDATA[") {
+            lt + find_end(rest, "]]>")
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            lt + find_end(rest, ">")
+        } else if let Some(tag) = &tag {
+            lt + tag.len
+        } else {
+            // A `<` that does not start a tag is text.
+            pos = lt + 1;
+            continue;
+        };
+
+        if skipped.is_none() && text_start < lt {
+            ranges.push((text_start, lt));
+        }
+
+        pos = end;
+        if let Some(tag) = tag {
+            skipped = tag.skip(skipped);
+            let raw: Option<&&str> = RAW_TEXT.iter().find(|raw| tag.is(raw));
+            if let Some(raw) = raw.filter(|_| !tag.closing && !tag.self_closing) {
+                // The content of a raw text element is never text that scriptures are searched in.
+                pos = find_closing(text, end, raw);
+            }
+        }
+        text_start = pos;
+    }
+
+    if skipped.is_none() && text_start < text.len() {
+        ranges.push((text_start, text.len()));
+    }
+
+    ranges
+}
+
+/// Returns the skipped element open after the inline HTML `fragment`, such as `<a href="/ge">` or `</code>`,
+/// given the one open before it. The text of a Markdown paragraph between such tags is left alone too.
+pub(crate) fn skip_inline(
+    fragment: &str,
+    skipped: Option<(&'static str, usize)>,
+) -> Option<(&'static str, usize)> {
+    match fragment
+        .starts_with('<')
+        .then(|| Tag::parse(fragment))
+        .flatten()
+    {
+        Some(tag) => tag.skip(skipped),
+        None => skipped,
+    }
+}
+
+/// Returns the byte ranges of the `<a>` elements in `text`, from the start of the opening tag to the end of the
+/// closing tag, in order. An element that is never closed runs to the end of `text`.
+pub(crate) fn anchors(text: &str) -> Vec<ScriptSlice> {
+    let mut ranges: Vec<ScriptSlice> = Vec::new();
+    let mut open: Option<usize> = None;
+    let mut pos: usize = 0;
+
+    while let Some(offset) = text[pos..].find('<') {
+        let lt: usize = pos + offset;
+        let Some(tag) = Tag::parse(&text[lt..]).filter(|tag| tag.is("a")) else {
+            pos = lt + 1;
+            continue;
+        };
+
+        pos = lt + tag.len;
+        match (open, tag.closing) {
+            (None, false) if !tag.self_closing => open = Some(lt),
+            (Some(start), true) => {
+                ranges.push((start, pos));
+                open = None;
+            }
+            _ => (),
+        }
+    }
+
+    if let Some(start) = open {
+        ranges.push((start, text.len()));
+    }
+
+    ranges
+}
+
+/// Returns the index after `needle` in `rest`, or the length of `rest` when the markup is never closed.
+fn find_end(rest: &str, needle: &str) -> usize {
+    rest.find(needle)
+        .map_or(rest.len(), |idx| idx + needle.len())
+}
+
+/// Returns the index of the closing tag of the raw text element `name`, searching from `from`.
+fn find_closing(text: &str, from: usize, name: &str) -> usize {
+    let bytes: &[u8] = text.as_bytes();
+    (from..bytes.len())
+        .find(|&idx| {
+            bytes[idx..].starts_with(b"</")
+                && bytes[idx + 2..]
+                    .get(..name.len())
+                    .is_some_and(|found| found.eq_ignore_ascii_case(name.as_bytes()))
+        })
+        .unwrap_or(text.len())
+}
+
+/// _Tag_ is an opening or closing tag, `<p class="x">` or `</p>`.
+struct Tag<'a> {
+    name: &'a str,
+    closing: bool,
+    self_closing: bool,
+    /// The length of the tag in bytes, including the angle brackets.
+    len: usize,
+}
+
+impl<'a> Tag<'a> {
+    /// Parses the tag at the start of `rest`, which starts with `<`.
+    fn parse(rest: &'a str) -> Option<Self> {
+        let closing: bool = rest[1..].starts_with('/');
+        let name_start: usize = if closing { 2 } else { 1 };
+        let name_len: usize = rest[name_start..]
+            .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '-' || ch == ':'))
+            .unwrap_or(rest.len() - name_start);
+        let name: &str = &rest[name_start..name_start + name_len];
+        if !name.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        // Attribute values may contain `>`, so quotes are followed to find the end of the tag.
+        let mut quote: Option<char> = None;
+        let mut len: usize = rest.len();
+        for (idx, ch) in rest.char_indices().skip(name_start + name_len) {
+            match (quote, ch) {
+                (None, '"' | '\'') => quote = Some(ch),
+                (Some(open), _) if open == ch => quote = None,
+                (None, '>') => {
+                    len = idx + 1;
+                    break;
+                }
+                _ => (),
+            }
+        }
+
+        Some(Self {
+            name,
+            closing,
+            self_closing: rest[..len].ends_with("/>"),
+            len,
+        })
+    }
+
+    fn is(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+    }
+
+    /// Returns the skipped element the tokenizer is inside of after this tag.
+    fn skip<'s>(&self, skipped: Option<(&'s str, usize)>) -> Option<(&'s str, usize)> {
+        match skipped {
+            None if !self.closing && !self.self_closing => SKIPPED
+                .iter()
+                .find(|name| self.is(name))
+                .map(|name| (*name, 1)),
+            Some((name, depth)) if self.is(name) && !self.self_closing => match self.closing {
+                true => (depth > 1).then(|| (name, depth - 1)),
+                false => Some((name, depth + 1)),
+            },
+            skipped => skipped,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn nodes(text: &str) -> Vec<&str> {
+        text_nodes(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+
+    #[test]
+    fn t_skip_attributes_and_links() {
+        let text: &str =
+            r#"<p title="John 3:16 > Ge 1:1">Read <b>Ro 8:28</b> or <a href="x">Re 21:4</a>.</p>"#;
+        assert_eq!(nodes(text), vec!["Read ", "Ro 8:28", " or ", "."]);
+    }
+
+    #[test]
+    fn t_skip_raw_text_and_comments() {
+        let text: &str =
+            "<style>p{}</style><SCRIPT>let a = '<b>John 3:16</b>';</script>x<!-- Ge 1:1 -->y";
+        assert_eq!(nodes(text), vec!["x", "y"]);
+    }
+
+    #[test]
+    fn t_nested_skipped() {
+        let text: &str = "<pre>a<pre>b</pre>c</pre>d <br/> 1 < 2 &amp; e";
+        assert_eq!(nodes(text), vec!["d ", " 1 < 2 &amp; e"]);
+    }
+
+    #[test]
+    fn t_anchors() {
+        let text: &str = r#"<A HREF="x">Re 21:4</A> <abbr>Ge</abbr> <a href='>'>Ro 8:28"#;
+        let got: Vec<&str> = anchors(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect();
+        assert_eq!(
+            got,
+            vec![r#"<A HREF="x">Re 21:4</A>"#, "<a href='>'>Ro 8:28"]
+        );
+    }
+}
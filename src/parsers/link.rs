@@ -0,0 +1,264 @@
+//! Recognizes scriptures that are already within the text of a link, `[see John 3:16](target)` in Markdown or the
+//! same link in any other [`LinkFormat`], or that are the text of a wiki link, `[[John 3:16]]`, so linking the same
+//! text again does not nest a new link inside it.
+use super::format::LinkFormat;
+use super::surround::ScriptSlice;
+
+/// _Link_ is an existing link found around a scripture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Link {
+    /// The whole link, markup included.
+    pub(crate) extent: ScriptSlice,
+    /// The URL the link points to, as written in the link.
+    pub(crate) target: ScriptSlice,
+    /// The markup of the link.
+    pub(crate) format: LinkFormat,
+}
+
+/// Returns the link in `format`, or else in Markdown, whose text holds the scripture at `slice`.
+pub(crate) fn around(text: &str, slice: &ScriptSlice, format: LinkFormat) -> Option<Link> {
+    let find = |format: LinkFormat| {
+        let (extent, target): (ScriptSlice, ScriptSlice) = match format {
+            LinkFormat::Markdown => markdown(text, slice),
+            LinkFormat::AsciiDoc => asciidoc(text, slice),
+            LinkFormat::RestructuredText => restructured_text(text, slice),
+            LinkFormat::Org => org(text, slice),
+            LinkFormat::Latex => latex(text, slice),
+            LinkFormat::BBCode => bbcode(text, slice),
+        }?;
+        Some(Link {
+            extent,
+            target,
+            format,
+        })
+    };
+
+    find(format).or_else(|| find(LinkFormat::Markdown))
+}
+
+/// Finds `[see John 3:16](target)`, returning the range of the whole link and of its target.
+fn markdown(text: &str, slice: &ScriptSlice) -> Option<(ScriptSlice, ScriptSlice)> {
+    let open: usize = open_before(text, slice.0, '[', ']')?;
+    let close: usize = close_after(text, slice.1, '[', ']')?;
+    if !text[close..].starts_with("](") {
+        return None;
+    }
+
+    let start: usize = close + "](".len();
+    let end: usize = start + text[start..].find(')')?;
+    Some(((open, end + 1), (start, end)))
+}
+
+/// Finds `link:target[see John 3:16]`, or the same link without `link:` when the target is a web address.
+fn asciidoc(text: &str, slice: &ScriptSlice) -> Option<(ScriptSlice, ScriptSlice)> {
+    let open: usize = open_before(text, slice.0, '[', ']')?;
+    let close: usize = close_after(text, slice.1, '[', ']')?;
+    let before: &str = &text[..open];
+    let start: usize = before.len()
+        - before
+            .chars()
+            .rev()
+            .take_while(|ch| !ch.is_whitespace())
+            .map(char::len_utf8)
+            .sum::<usize>();
+
+    let macro_: &str = &text[start..open];
+    let target: usize = match macro_.strip_prefix("link:") {
+        Some(url) if !url.is_empty() => start + "link:".len(),
+        Some(_) => return None,
+        None if macro_.starts_with("https://") || macro_.starts_with("http://") => start,
+        None => return None,
+    };
+    Some(((start, close + 1), (target, open)))
+}
+
+/// Finds `` `see John 3:16 <target>`_ ``, or the anonymous link ending in `` `__ ``.
+fn restructured_text(text: &str, slice: &ScriptSlice) -> Option<(ScriptSlice, ScriptSlice)> {
+    let open: usize = open_before(text, slice.0, '`', '`')?;
+    let tick: usize = slice.1 + text[slice.1..].find('`')?;
+    if !text[..tick].ends_with('>') || !text[tick..].starts_with("`_") {
+        return None;
+    }
+
+    let start: usize = slice.1 + text[slice.1..tick].rfind('<')? + 1;
+    let end: usize = match text[tick..].starts_with("`__") {
+        true => tick + "`__".len(),
+        false => tick + "`_".len(),
+    };
+    Some(((open, end), (start, tick - 1)))
+}
+
+/// Finds `[[target][see John 3:16]]`.
+fn org(text: &str, slice: &ScriptSlice) -> Option<(ScriptSlice, ScriptSlice)> {
+    let open: usize = open_before(text, slice.0, '[', ']')?;
+    let close: usize = close_after(text, slice.1, '[', ']')?;
+    if !text[..open].ends_with(']') || !text[close..].starts_with("]]") {
+        return None;
+    }
+
+    let end: usize = open - 1;
+    let start: usize = text[..end].rfind("[[")? + "[[".len();
+    if text[start..end].contains(['[', ']']) {
+        return None;
+    }
+    Some(((start - "[[".len(), close + "]]".len()), (start, end)))
+}
+
+/// Finds `\href{target}{see John 3:16}`.
+fn latex(text: &str, slice: &ScriptSlice) -> Option<(ScriptSlice, ScriptSlice)> {
+    let open: usize = open_before(text, slice.0, '{', '}')?;
+    let close: usize = close_after(text, slice.1, '{', '}')?;
+    if !text[..open].ends_with('}') {
+        return None;
+    }
+
+    let end: usize = open - 1;
+    let start: usize = text[..end].rfind("\\href{")? + "\\href{".len();
+    if text[start..end].contains("}{") {
+        return None;
+    }
+    Some(((start - "\\href{".len(), close + 1), (start, end)))
+}
+
+/// Finds `[url=target]see John 3:16[/url]`.
+fn bbcode(text: &str, slice: &ScriptSlice) -> Option<(ScriptSlice, ScriptSlice)> {
+    let end: usize = text[..slice.0].rfind(['[', ']'])?;
+    let close: usize = slice.1 + text[slice.1..].find('[')?;
+    if !text[end..].starts_with(']') || !text[close..].starts_with("[/url]") {
+        return None;
+    }
+
+    let start: usize = text[..end].rfind("[url=")? + "[url=".len();
+    if text[start..end].contains(['[', ']']) {
+        return None;
+    }
+    Some(((start - "[url=".len(), close + "[/url]".len()), (start, end)))
+}
+
+/// Returns the range taken up by the scripture at `slice` together with the link around it, if any.
+/// When the link is not closed yet, e.g. the rest of it has not been read, the range runs to the end of `text`.
+pub(crate) fn extent(text: &str, slice: &ScriptSlice) -> ScriptSlice {
+    if let Some((extent, _)) = markdown(text, slice) {
+        return extent;
+    }
+    let Some(open) = open_before(text, slice.0, '[', ']') else {
+        return *slice;
+    };
+
+    let rest: &str = &text[slice.1..];
+    let unclosed: bool = match rest.find(['[', ']']) {
+        Some(close) if rest[close..].starts_with(']') => {
+            rest[close..] == *"]" || (rest[close..].starts_with("](") && !rest[close..].contains(')'))
+        }
+        Some(_) => false,
+        None => true,
+    };
+    match unclosed {
+        true => (open, text.len()),
+        false => *slice,
+    }
+}
+
+/// True if the scripture at `slice` is the text of a wiki link, `[[John 3:16]]` or `[[John 3#16|John 3:16]]`.
+pub(crate) fn in_wiki_link(text: &str, slice: &ScriptSlice) -> bool {
+    let before: &str = &text[..slice.0];
+    let after: &str = &text[slice.1..];
+    (before.ends_with("[[") || before.ends_with('|'))
+        && (after.starts_with("]]") || after.starts_with('|'))
+}
+
+/// Returns the index of the last `open` before `idx`, if no `open` or `close` comes between the two.
+fn open_before(text: &str, idx: usize, open: char, close: char) -> Option<usize> {
+    let found: usize = text[..idx].rfind([open, close])?;
+    text[found..].starts_with(open).then_some(found)
+}
+
+/// Returns the index of the first `close` after `idx`, if no `open` comes before it.
+fn close_after(text: &str, idx: usize, open: char, close: char) -> Option<usize> {
+    let found: usize = idx + text[idx..].find([open, close])?;
+    text[found..].starts_with(close).then_some(found)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn t_around() {
+        let text: &str = "See [John 3:16](https://example.com/john) and John 3:16.";
+        let target =
+            |slice: ScriptSlice| around(text, &slice, LinkFormat::Markdown).map(|link| link.target);
+        assert_eq!(target((5, 14)), Some((16, 40)));
+        assert_eq!(target((46, 55)), None);
+    }
+
+    #[test]
+    fn t_around_within_text() {
+        let text: &str = "[see John 3:16 here](https://example.com) [[John 3:16]] [John 3:16] (x)";
+        let found = |slice: ScriptSlice| around(text, &slice, LinkFormat::Org);
+        assert_eq!(
+            found((5, 14)),
+            Some(Link {
+                extent: (0, 41),
+                target: (21, 40),
+                format: LinkFormat::Markdown,
+            })
+        );
+        assert_eq!(found((44, 53)), None);
+        assert_eq!(found((57, 66)), None);
+    }
+
+    #[test]
+    fn t_around_every_format() {
+        let links: [(LinkFormat, &str); 7] = [
+            (LinkFormat::AsciiDoc, "link:https://x.org/a[see John 3:16]"),
+            (LinkFormat::AsciiDoc, "https://x.org/a[see John 3:16]"),
+            (LinkFormat::RestructuredText, "`see John 3:16 <https://x.org/a>`_"),
+            (LinkFormat::RestructuredText, "`see John 3:16 <https://x.org/a>`__"),
+            (LinkFormat::Org, "[[https://x.org/a][see John 3:16]]"),
+            (LinkFormat::Latex, "\\href{https://x.org/a}{see John 3:16}"),
+            (LinkFormat::BBCode, "[url=https://x.org/a]see John 3:16[/url]"),
+        ];
+        for (format, link) in links {
+            let text: String = format!("Read {link}.");
+            let start: usize = text.find("John").unwrap();
+            let got: Link = around(&text, &(start, start + 9), format).unwrap();
+            assert_eq!(&text[got.extent.0..got.extent.1], link);
+            assert_eq!(&text[got.target.0..got.target.1], "https://x.org/a");
+            assert_eq!(got.format, format);
+        }
+    }
+
+    #[test]
+    fn t_around_not_a_link() {
+        let texts: [(LinkFormat, &str); 5] = [
+            (LinkFormat::AsciiDoc, "Note[see John 3:16]"),
+            (LinkFormat::RestructuredText, "`see John 3:16`_"),
+            (LinkFormat::Org, "[[see John 3:16]]"),
+            (LinkFormat::Latex, "\\emph{see John 3:16}"),
+            (LinkFormat::BBCode, "[b]see John 3:16[/b]"),
+        ];
+        for (format, text) in texts {
+            let start: usize = text.find("John").unwrap();
+            assert_eq!(around(text, &(start, start + 9), format), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn t_in_wiki_link() {
+        let text: &str = "[[John 3:16]] [[John 3#16|John 3:16]] [John 3:16]";
+        assert!(in_wiki_link(text, &(2, 11)));
+        assert!(in_wiki_link(text, &(26, 35)));
+        assert!(!in_wiki_link(text, &(39, 48)));
+    }
+
+    #[test]
+    fn t_extent_unclosed() {
+        let text: &str = "See [John 3:16](https://exam";
+        assert_eq!(extent(text, &(5, 14)), (4, text.len()));
+        assert_eq!(extent("[John 3:16](x)", &(1, 10)), (0, 14));
+        assert_eq!(extent("[see John 3:16 and", &(5, 14)), (0, 18));
+        assert_eq!(extent("[see John 3:16] and", &(5, 14)), (5, 14));
+    }
+}
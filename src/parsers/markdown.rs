@@ -0,0 +1,134 @@
+//! Finds the prose of a CommonMark document, so scriptures inside code, links, images, HTML, including the text
+//! of inline `<a>`, `<code>` and `<pre>` elements, and front matter can be left untouched, and the text of its links, so links to a supported site can be refreshed.
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use super::html;
+use super::surround::ScriptSlice;
+
+/// Returns the byte ranges of the prose text in a Markdown document, then those of the text of its links, in order.
+/// Adjacent pieces of text, including line breaks within a paragraph, are merged into one range so a
+/// scripture written over two lines is still found.
+pub(crate) fn prose(text: &str) -> (Vec<ScriptSlice>, Vec<ScriptSlice>) {
+    let options: Options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_WIKILINKS
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+        | Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS;
+
+    let mut ranges: Vec<ScriptSlice> = Vec::new();
+    let mut links: Vec<ScriptSlice> = Vec::new();
+    // The number of elements the parser is currently inside of whose text must be left alone, and how many of
+    // them are links.
+    let mut skip: usize = 0;
+    let mut link: usize = 0;
+    // The inline HTML element open in the paragraph whose text must be left alone, see `html::skip_inline`.
+    let mut inline: Option<(&str, usize)> = None;
+
+    for (event, range) in Parser::new_ext(text, options).into_offset_iter() {
+        match event {
+            Event::Start(tag) if is_skipped(&tag) => {
+                skip += 1;
+                link += usize::from(matches!(tag, Tag::Link { .. }));
+            }
+            Event::End(tag) if is_skipped_end(&tag) => {
+                skip -= 1;
+                link -= usize::from(tag == TagEnd::Link);
+            }
+            // An inline element left open ends with its paragraph.
+            Event::End(TagEnd::Paragraph) => inline = None,
+            Event::InlineHtml(fragment) => inline = html::skip_inline(&fragment, inline),
+            Event::Text(_) | Event::SoftBreak | Event::HardBreak if inline.is_some() => (),
+            Event::Text(_) | Event::SoftBreak | Event::HardBreak if skip == 0 => {
+                merge(&mut ranges, range.start, range.end)
+            }
+            Event::Text(_) | Event::SoftBreak | Event::HardBreak if skip == link => {
+                merge(&mut links, range.start, range.end)
+            }
+            _ => (),
+        }
+    }
+
+    (ranges, links)
+}
+
+/// Pushes the range from `start` to `end` onto `ranges`, merged with the last one when they touch.
+fn merge(ranges: &mut Vec<ScriptSlice>, start: usize, end: usize) {
+    match ranges.last_mut() {
+        Some(last) if last.1 == start => last.1 = end,
+        _ => ranges.push((start, end)),
+    }
+}
+
+fn is_skipped(tag: &Tag) -> bool {
+    matches!(
+        tag,
+        Tag::CodeBlock(_)
+            | Tag::HtmlBlock
+            | Tag::Link { .. }
+            | Tag::Image { .. }
+            | Tag::MetadataBlock(_)
+    )
+}
+
+fn is_skipped_end(tag: &TagEnd) -> bool {
+    matches!(
+        tag,
+        TagEnd::CodeBlock
+            | TagEnd::HtmlBlock
+            | TagEnd::Link
+            | TagEnd::Image
+            | TagEnd::MetadataBlock(_)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn prose_text(text: &str) -> Vec<&str> {
+        prose(text)
+            .0
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+
+    #[test]
+    fn t_prose_skips_code_and_links() {
+        let text: &str =
+            "Read *John 3:16* and `Ge 1:1`, [Re 21:4](https://jw.org).\n\n```\nPs 83:18\n```\n";
+        assert_eq!(
+            prose_text(text),
+            vec!["Read ", "John 3:16", " and ", ", ", "."]
+        );
+    }
+
+    #[test]
+    fn t_prose_skips_front_matter() {
+        let text: &str = "---\ntitle: John 3:16\n---\nRead\nJohn 3:16\n";
+        assert_eq!(prose_text(text), vec!["Read\nJohn 3:16"]);
+    }
+
+    #[test]
+    fn t_prose_skips_inline_html() {
+        let text: &str = "Read <a href=\"/jn\">John 3:16</a>, <code>Ge 1:1</code> and <b>Ro 8:28</b>.\n\n<a>Re 21:4\n\nPs 83:18";
+        assert_eq!(
+            prose_text(text),
+            vec!["Read ", ", ", " and ", "Ro 8:28", ".", "Ps 83:18"]
+        );
+    }
+
+    #[test]
+    fn t_prose_link_text() {
+        let text: &str = "Read [see *John 3:16*](https://jw.org), ![Ge 1:1](x.png) and [`Re 21:4`](y).";
+        let got: Vec<&str> = prose(text)
+            .1
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect();
+        assert_eq!(got, vec!["see ", "John 3:16"]);
+    }
+}
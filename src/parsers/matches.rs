@@ -0,0 +1,192 @@
+use crate::locales::{
+    nwt_en::{Book, Lookup, Site},
+    BibleError,
+};
+use crate::url::Url;
+
+use super::{
+    reference::Reference,
+    scanner::{scan, Scanner},
+    surround::{contains, ScriptSlice},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// _Match_ is a _potential_ scripture found in a string, it borrows from the string that was searched.
+/// The scripture is only parsed when [`Match::reference`] is called.
+pub struct Match<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lookup: Lookup,
+}
+
+impl<'a> Match<'a> {
+    /// The byte index where the scripture starts.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The byte index where the scripture ends, plus one.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The start and end index of the scripture.
+    pub fn slice(&self) -> ScriptSlice {
+        (self.start, self.end)
+    }
+
+    /// The scripture as it appears in the string.
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Parses the scripture, returning an error if the book does not exist.
+    /// Only the first chapter is returned for scriptures such as `John 3:16; 4:1`, see [`Match::references`].
+    pub fn reference(&self) -> Result<Reference, BibleError> {
+        Reference::parse_with(self.text, self.lookup)
+    }
+
+    /// Parses the scripture into one reference per chapter.
+    pub fn references(&self) -> Result<Vec<Reference>, BibleError> {
+        Reference::parse_all(self.text, self.lookup)
+    }
+
+    /// Returns the URL of the scripture on `site`, returning an error if the book does not exist.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::locales::nwt_en::Site;
+    ///
+    /// let found = est::references("Read Joh 3:16.").next().unwrap();
+    /// let expect: &str = "https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016";
+    /// assert_eq!(found.url(&Site::JwOrg).unwrap(), expect);
+    /// ```
+    pub fn url(&self, site: &Site) -> Result<String, BibleError> {
+        site.get_url(&self.reference()?)
+    }
+
+    /// Returns every problem with the scripture: a book that does not exist or whose abbreviation is ambiguous,
+    /// a chapter or verse that is not in the book or a range of verses that ends before it starts. The
+    /// scripture is correct when the list is empty.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::locales::BibleError;
+    ///
+    /// let found = est::references("Read Jud 1:4 and John 3:16-12; 22:1.").collect::<Vec<_>>();
+    /// assert_eq!(found[0].check()[0].to_string(), "the Bible book, Jud, is ambiguous, it could be Judges, Jude");
+    /// assert_eq!(found[1].check(), vec![
+    ///     BibleError::InvalidRange("John 3:16-12".to_string()),
+    ///     BibleError::ChapterNotFound("John".to_string(), 22),
+    /// ]);
+    /// ```
+    pub fn check(&self) -> Vec<BibleError> {
+        match self.references() {
+            Ok(references) => references
+                .iter()
+                .filter_map(|reference| reference.validate().err())
+                .collect(),
+            Err(BibleError::BookNotFound(name)) => {
+                let books: Vec<Book> = Book::starting_with(&name);
+                match books.len() > 1 {
+                    true => {
+                        let names: Vec<String> = books
+                            .iter()
+                            .map(|book| book.canonical_name().to_string())
+                            .collect();
+                        vec![BibleError::AmbiguousBook(name, names)]
+                    }
+                    false => vec![BibleError::BookNotFound(name)],
+                }
+            }
+            Err(e) => vec![e],
+        }
+    }
+
+    /// True if the match contains a valid Bible book name.
+    pub fn is_valid(&self) -> bool {
+        self.reference().is_ok()
+    }
+}
+
+/// _References_ is a lazy iterator over the potential scriptures in a string, see [`crate::references`].
+#[derive(Debug)]
+pub struct References<'a> {
+    text: &'a str,
+    matches: Scanner<'a>,
+    lookup: Lookup,
+    /// When set, only the scriptures that lie within these ranges are returned.
+    within: Option<&'a [ScriptSlice]>,
+}
+
+impl<'a> References<'a> {
+    pub(crate) fn new(text: &'a str, lookup: Lookup) -> Self {
+        Self {
+            text,
+            matches: scan(text),
+            lookup,
+            within: None,
+        }
+    }
+
+    /// Only returns the scriptures that lie within `ranges`, which must be in order.
+    pub(crate) fn within(mut self, ranges: &'a [ScriptSlice]) -> Self {
+        self.within = Some(ranges);
+        self
+    }
+}
+
+impl<'a> Iterator for References<'a> {
+    type Item = Match<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let within: Option<&[ScriptSlice]> = self.within;
+        let mut matches = self.matches.by_ref().filter(|found| {
+            within.is_none_or(|ranges| contains(ranges, &(found.start, found.end)))
+        });
+
+        matches.next().map(|found| Match {
+            text: &self.text[found.start..found.end],
+            start: found.start,
+            end: found.end,
+            lookup: self.lookup,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::locales::nwt_en::Book;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn t_lazy_matches() {
+        let text: &str = "John 3:16 and Mary 1:1 and Re 12:12";
+        let got: Vec<(ScriptSlice, &str, bool)> = References::new(text, Lookup::default())
+            .map(|m| (m.slice(), m.as_str(), m.is_valid()))
+            .collect();
+        let expect = vec![
+            ((0, 9), "John 3:16", true),
+            ((14, 22), "Mary 1:1", false),
+            ((27, 35), "Re 12:12", true),
+        ];
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn t_ignore_diacritics() {
+        let text: &str = "Génesis 1:1";
+        assert!(!References::new(text, Lookup::default()).next().unwrap().is_valid());
+
+        let lookup: Lookup = Lookup {
+            ignore_diacritics: true,
+            ..Lookup::default()
+        };
+        let got = References::new(text, lookup).next().unwrap().reference().unwrap();
+        assert_eq!(got.book, Book::Genesis);
+    }
+}
@@ -1,2 +1,16 @@
+pub mod anchor;
+pub mod bcv;
+pub mod format;
+pub mod html;
+pub mod link;
+pub mod markdown;
+pub mod matches;
+pub mod osis;
+pub mod position;
+pub mod reference;
+pub mod scanner;
 pub mod scripture;
+pub mod stream;
+pub mod style;
 pub mod surround;
+pub mod wiki;
@@ -0,0 +1,213 @@
+use crate::locales::{nwt_en::Book, BibleError};
+
+use super::reference::{Reference, VerseRange};
+use super::style::Style;
+
+/// A single OSIS ID such as `John.3.16` or `John.3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct OsisId {
+    book: Book,
+    chapter: u16,
+    verse: Option<u16>,
+}
+
+impl Reference {
+    /// Parses an OSIS reference such as `John.3.16`, `Gen.1.1-Gen.2.3` or a space separated list of them into
+    /// one `Reference` per chapter. A range that runs into the next chapter is split at the last verse of the
+    /// chapter, and consecutive IDs within the same chapter are merged.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::{Reference, VerseRange};
+    /// use est::locales::nwt_en::Book;
+    ///
+    /// let got: Vec<Reference> = Reference::from_osis("Gen.1.31-Gen.2.3").unwrap();
+    /// assert_eq!(got[0], Reference { book: Book::Genesis, chapter: 1, verses: vec![VerseRange { start: 31, end: 31 }] });
+    /// assert_eq!(got[1], Reference { book: Book::Genesis, chapter: 2, verses: vec![VerseRange { start: 1, end: 3 }] });
+    /// ```
+    pub fn from_osis(osis: &str) -> Result<Vec<Reference>, BibleError> {
+        let mut references: Vec<Reference> = Vec::new();
+
+        for part in osis.split_whitespace() {
+            let (start, end) = match part.split_once('-') {
+                Some((start, end)) => (OsisId::parse(start)?, OsisId::parse(end)?),
+                None => (OsisId::parse(part)?, OsisId::parse(part)?),
+            };
+            // Ranges across books or between a chapter and a verse are not supported.
+            if start.book != end.book || start.verse.is_some() != end.verse.is_some() {
+                return Err(BibleError::ParsingError(part.to_string()));
+            }
+            if (start.chapter, start.verse) > (end.chapter, end.verse) {
+                return Err(BibleError::InvalidRange(part.to_string()));
+            }
+
+            push_span(
+                &mut references,
+                start.book,
+                (start.chapter, start.verse),
+                (end.chapter, end.verse),
+            )?;
+        }
+
+        match references.is_empty() {
+            true => Err(BibleError::ParsingError(osis.to_string())),
+            false => Ok(references),
+        }
+    }
+
+    /// Returns the reference as OSIS, `1Cor.13.4-1Cor.13.7 1Cor.13.13`, see [`Style::Osis`].
+    ///
+    /// ## Example
+    /// ```
+    /// use est::Reference;
+    ///
+    /// let reference: Reference = Reference::parse("John 3:16-18").unwrap();
+    /// assert_eq!(reference.to_osis(), "John.3.16-John.3.18");
+    /// assert_eq!(Reference::from_osis(&reference.to_osis()).unwrap(), vec![reference]);
+    /// ```
+    pub fn to_osis(&self) -> String {
+        self.display(Style::Osis).to_string()
+    }
+}
+
+impl OsisId {
+    /// Parses `Book.chapter` or `Book.chapter.verse`.
+    fn parse(id: &str) -> Result<Self, BibleError> {
+        let invalid = || BibleError::ParsingError(id.to_string());
+        let number = |digits: &str| digits.parse::<u16>().map_err(|_| invalid());
+
+        let mut parts = id.split('.');
+        let book: Book = Book::from_osis(parts.next().unwrap_or_default())?;
+        let chapter: u16 = number(parts.next().ok_or_else(invalid)?)?;
+        let verse: Option<u16> = parts.next().map(number).transpose()?;
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            book,
+            chapter,
+            verse,
+        })
+    }
+}
+
+/// Pushes the span of `book` from `start` to `end`, a chapter and an optional verse each, onto `references`, one
+/// reference per chapter. A span of verses that runs into the next chapter is split at the last verse of the chapter.
+pub(crate) fn push_span(
+    references: &mut Vec<Reference>,
+    book: Book,
+    start: (u16, Option<u16>),
+    end: (u16, Option<u16>),
+) -> Result<(), BibleError> {
+    for chapter in start.0..=end.0 {
+        // A span across chapters stops at the first chapter the book does not have, before it could grow
+        // unbounded. A single chapter is not validated, like any other parsed reference.
+        let chapter_end: Option<u16> = book.verses(chapter);
+        if chapter_end.is_none() && start.0 != end.0 {
+            let name: &str = book.canonical_name();
+            return Err(BibleError::ChapterNotFound(name.to_string(), chapter));
+        }
+        let verses: Option<VerseRange> = match (start.1, end.1) {
+            (Some(first), Some(last)) => Some(VerseRange {
+                start: match chapter == start.0 {
+                    true => first,
+                    false => 1,
+                },
+                end: match chapter == end.0 {
+                    true => last,
+                    false => chapter_end.unwrap_or(last),
+                },
+            }),
+            _ => None,
+        };
+        push(references, book, chapter, verses);
+    }
+
+    Ok(())
+}
+
+/// Pushes `verses` of `chapter` onto `references`, adding them to the last reference when it is the same chapter.
+fn push(references: &mut Vec<Reference>, book: Book, chapter: u16, verses: Option<VerseRange>) {
+    match references.last_mut() {
+        Some(last) if last.book == book && last.chapter == chapter => last.verses.extend(verses),
+        _ => references.push(Reference {
+            book,
+            chapter,
+            verses: verses.into_iter().collect(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn t_from_osis_list() {
+        let got: Vec<Reference> =
+            Reference::from_osis("1Cor.13.4-1Cor.13.7 1Cor.13.13 Ps.23").unwrap();
+        let expect: Vec<Reference> = vec![
+            Reference {
+                book: Book::FirstCorinthians,
+                chapter: 13,
+                verses: vec![
+                    VerseRange { start: 4, end: 7 },
+                    VerseRange { start: 13, end: 13 },
+                ],
+            },
+            Reference {
+                book: Book::Psalms,
+                chapter: 23,
+                verses: vec![],
+            },
+        ];
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn t_from_osis_chapters() {
+        let got: Vec<u16> = Reference::from_osis("Jude.1.25-Jude.1.25 Ps.1.6-Ps.3.1")
+            .unwrap()
+            .iter()
+            .flat_map(|reference| reference.verses.iter().map(|verses| verses.end))
+            .collect();
+        assert_eq!(got, vec![25, 6, 12, 1]);
+    }
+
+    #[test]
+    fn t_from_osis_errors() {
+        let parse = |osis: &str| Reference::from_osis(osis).unwrap_err();
+        assert_eq!(parse("Jn.3.16"), BibleError::BookNotFound("Jn".to_string()));
+        assert_eq!(
+            parse("John.3.x"),
+            BibleError::ParsingError("John.3.x".to_string())
+        );
+        assert_eq!(
+            parse("John.3.16.1"),
+            BibleError::ParsingError("John.3.16.1".to_string())
+        );
+        assert_eq!(
+            parse("Gen.50.1-Exod.1.1"),
+            BibleError::ParsingError("Gen.50.1-Exod.1.1".to_string())
+        );
+        assert_eq!(
+            parse("John.3.18-John.3.16"),
+            BibleError::InvalidRange("John.3.18-John.3.16".to_string())
+        );
+        assert_eq!(
+            parse("Jude.1.1-Jude.3.1"),
+            BibleError::ChapterNotFound("Jude".to_string(), 2)
+        );
+        assert_eq!(
+            parse("Gen.1-Gen.50000"),
+            BibleError::ChapterNotFound("Genesis".to_string(), 51)
+        );
+        assert_eq!(
+            parse("Ps.150-Ps.151"),
+            BibleError::ChapterNotFound("Psalms".to_string(), 151)
+        );
+        assert_eq!(parse(" "), BibleError::ParsingError(" ".to_string()));
+    }
+}
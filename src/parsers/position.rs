@@ -0,0 +1,119 @@
+use super::surround::ScriptSlice;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// _Position_ describes a single offset within a string in the units used by different tools.
+///
+/// Take the string "Já John 3:16" as an example, the `J` of John is found at:
+/// - byte = 4 (`á` takes two bytes in UTF-8)
+/// - char = 3
+/// - utf16 = 3
+/// - line = 1, column = 4, utf16_column = 4
+pub struct Position {
+    /// Offset in bytes of the UTF-8 string, the same unit as [`ScriptSlice`].
+    pub byte: usize,
+    /// Offset in Unicode scalar values (code points), as used by Python strings.
+    pub char: usize,
+    /// Offset in UTF-16 code units, as used by JavaScript strings and most editors.
+    pub utf16: usize,
+    /// The line number, starting at `1`. Lines are separated by `\n`.
+    pub line: usize,
+    /// The column within the line counted in chars, starting at `1`.
+    pub column: usize,
+    /// The column within the line counted in UTF-16 code units, starting at `1`. Editors such as VS Code and the
+    /// Language Server Protocol count columns this way, the LSP `character` is this minus one.
+    pub utf16_column: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// _Span_ holds the start and end (+1) [`Position`] of a scripture found in a string.
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// Converts the byte based `slices` into spans in a single pass over `text`.
+/// The slices must be ordered and fall on char boundaries, which is the case for all slices found by the parser.
+pub(crate) fn spans(text: &str, slices: &[ScriptSlice]) -> Vec<Span> {
+    let mut spans: Vec<Span> = Vec::with_capacity(slices.len());
+    let mut pos: Position = Position {
+        line: 1,
+        column: 1,
+        utf16_column: 1,
+        ..Default::default()
+    };
+    let mut chars = text.char_indices().peekable();
+
+    let mut advance_to = |target: usize, pos: &mut Position| {
+        while let Some((idx, ch)) = chars.next_if(|(idx, _)| *idx < target) {
+            pos.byte = idx + ch.len_utf8();
+            pos.char += 1;
+            pos.utf16 += ch.len_utf16();
+            if ch == '\n' {
+                pos.line += 1;
+                pos.column = 1;
+                pos.utf16_column = 1;
+            } else {
+                pos.column += 1;
+                pos.utf16_column += ch.len_utf16();
+            }
+        }
+        *pos
+    };
+
+    for (start, end) in slices {
+        let start: Position = advance_to(*start, &mut pos);
+        let end: Position = advance_to(*end, &mut pos);
+        spans.push(Span { start, end });
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn t_ascii() {
+        let got: Vec<Span> = spans("See John 3:16.", &[(4, 13)]);
+        let expect: Span = Span {
+            start: Position { byte: 4, char: 4, utf16: 4, line: 1, column: 5, utf16_column: 5 },
+            end: Position { byte: 13, char: 13, utf16: 13, line: 1, column: 14, utf16_column: 14 },
+        };
+        assert_eq!(got, vec![expect]);
+    }
+
+    #[test]
+    fn t_multibyte_and_lines() {
+        // `é` is 2 bytes and 1 UTF-16 unit, `𝄞` is 4 bytes and 2 UTF-16 units.
+        let text: &str = "é𝄞\nRead John 3:16";
+        let got: Vec<Span> = spans(text, &[(12, 21)]);
+        let expect: Span = Span {
+            start: Position { byte: 12, char: 8, utf16: 9, line: 2, column: 6, utf16_column: 6 },
+            end: Position { byte: 21, char: 17, utf16: 18, line: 2, column: 15, utf16_column: 15 },
+        };
+        assert_eq!(got, vec![expect]);
+    }
+
+    #[test]
+    fn t_utf16_column() {
+        let got: Vec<(usize, usize)> = spans("𝄞 John 3:16", &[(5, 14)])
+            .iter()
+            .map(|span| (span.start.column, span.start.utf16_column))
+            .collect();
+        assert_eq!(got, vec![(3, 4)]);
+    }
+
+    #[test]
+    fn t_multiple_slices() {
+        let text: &str = "John 3:16\nRe 12:12";
+        let got: Vec<(usize, usize)> = spans(text, &[(0, 9), (10, 18)])
+            .iter()
+            .map(|span| (span.start.line, span.start.column))
+            .collect();
+        assert_eq!(got, vec![(1, 1), (2, 1)]);
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt;
+
+use crate::locales::{
+    nwt_en::{Book, Lookup},
+    BibleError,
+};
+
+use super::scripture::Bible;
+use super::style::{Style, Styled};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// _VerseRange_ describes an inclusive range of verses, a single verse has the same `start` and `end`.
+pub struct VerseRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// _Reference_ is a parsed scripture within a single chapter of a book.
+///
+/// Take `1 Corinthians 13:4-7, 13` as an example, the reference will be as follows:
+/// - book = `Book::FirstCorinthians`
+/// - chapter = 13
+/// - verses = `[4-7, 13-13]`
+pub struct Reference {
+    pub book: Book,
+    pub chapter: u16,
+    /// The verses referenced, empty when no verse is given.
+    pub verses: Vec<VerseRange>,
+}
+
+impl Reference {
+    /// Parses a single scripture such as `John 3:16` into a `Reference`.
+    /// When the scripture continues into other chapters, e.g. `John 3:16; 4:1`, only the first chapter is returned.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::{Reference, VerseRange};
+    /// use est::locales::nwt_en::Book;
+    ///
+    /// let got = Reference::parse("Joh 3:16-18").unwrap();
+    /// assert_eq!(got.book, Book::John);
+    /// assert_eq!(got.chapter, 3);
+    /// assert_eq!(got.verses, vec![VerseRange { start: 16, end: 18 }]);
+    /// ```
+    pub fn parse(scripture: &str) -> Result<Reference, BibleError> {
+        Self::parse_with(scripture, Lookup::default())
+    }
+
+    /// Returns the reference written in `style`, for use with `format!` and `to_string()`. The names of the
+    /// books are those of the locale of [`Book`]. Displaying a `Reference` without a style uses [`Style::Full`].
+    ///
+    /// ## Example
+    /// ```
+    /// use est::{Reference, Style};
+    ///
+    /// let reference: Reference = Reference::parse("1 corinthians 13:4-7,13").unwrap();
+    /// assert_eq!(reference.to_string(), "1 Corinthians 13:4-7, 13");
+    /// assert_eq!(reference.display(Style::Compact).to_string(), "1Co 13:4-7,13");
+    /// assert_eq!(format!("{}", reference.display(Style::Osis)), "1Cor.13.4-1Cor.13.7 1Cor.13.13");
+    /// ```
+    pub fn display(&self, style: Style) -> Styled<'_> {
+        Styled {
+            reference: self,
+            style,
+        }
+    }
+
+    /// Checks that the chapter and verses exist in the book and that no range of verses ends before it starts.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::Reference;
+    /// use est::locales::BibleError;
+    ///
+    /// assert_eq!(Reference::parse("John 3:16").unwrap().validate(), Ok(()));
+    /// let got = Reference::parse("John 3:37").unwrap().validate();
+    /// assert_eq!(got, Err(BibleError::VerseNotFound("John".to_string(), 3, 37)));
+    /// ```
+    pub fn validate(&self) -> Result<(), BibleError> {
+        let name: &str = self.book.canonical_name();
+        let verses: u16 = self
+            .book
+            .verses(self.chapter)
+            .ok_or_else(|| BibleError::ChapterNotFound(name.to_string(), self.chapter))?;
+
+        for range in &self.verses {
+            if range.start > range.end {
+                let range: String =
+                    format!("{name} {}:{}-{}", self.chapter, range.start, range.end);
+                return Err(BibleError::InvalidRange(range));
+            }
+            for verse in [range.start, range.end] {
+                if verse == 0 || verse > verses {
+                    return Err(BibleError::VerseNotFound(
+                        name.to_string(),
+                        self.chapter,
+                        verse,
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Same as [`Reference::parse`], looking up the book name as set by `lookup`.
+    pub(crate) fn parse_with(scripture: &str, lookup: Lookup) -> Result<Reference, BibleError> {
+        Self::parse_all(scripture, lookup)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| BibleError::ParsingError(scripture.to_string()))
+    }
+
+    /// Parses a scripture into one `Reference` per chapter, `John 3:16; 4:1` gives two references.
+    pub(crate) fn parse_all(scripture: &str, lookup: Lookup) -> Result<Vec<Reference>, BibleError> {
+        let bible: Bible = Bible::parse_with(scripture, lookup)?;
+        let book: Book = bible.get_book_enum()?;
+        let mut segments = bible.get_verse().split(';');
+
+        let mut references: Vec<Reference> = vec![Reference {
+            book,
+            chapter: number(bible.get_chapter(), scripture)?,
+            verses: verses(segments.next().unwrap_or_default(), scripture)?,
+        }];
+
+        for segment in segments {
+            let (chapter, list) = segment
+                .split_once(':')
+                .ok_or_else(|| BibleError::ParsingError(scripture.to_string()))?;
+
+            references.push(Reference {
+                book,
+                chapter: number(chapter, scripture)?,
+                verses: verses(list, scripture)?,
+            });
+        }
+
+        Ok(references)
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(Style::Full).fmt(f)
+    }
+}
+
+/// Parses a list of verses such as `16, 17-20` into ranges.
+fn verses(list: &str, scripture: &str) -> Result<Vec<VerseRange>, BibleError> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|verse| !verse.is_empty())
+        .map(|verse| {
+            let (start, end) = verse.split_once(['-', '–', '—']).unwrap_or((verse, verse));
+            Ok(VerseRange {
+                start: number(start, scripture)?,
+                end: number(end, scripture)?,
+            })
+        })
+        .collect()
+}
+
+fn number(digits: &str, scripture: &str) -> Result<u16, BibleError> {
+    digits
+        .trim()
+        .parse()
+        .map_err(|_| BibleError::ParsingError(scripture.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn t_parse_single() {
+        let got: Reference = Reference::parse("Psalms 23:1").unwrap();
+        let expect: Reference = Reference {
+            book: Book::Psalms,
+            chapter: 23,
+            verses: vec![VerseRange { start: 1, end: 1 }],
+        };
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn t_parse_list() {
+        let got: Reference = Reference::parse("Psalms 83:18, 17—20").unwrap();
+        let expect: Vec<VerseRange> = vec![
+            VerseRange { start: 18, end: 18 },
+            VerseRange { start: 17, end: 20 },
+        ];
+        assert_eq!(got.verses, expect);
+    }
+
+    #[test]
+    fn t_parse_all_chapters() {
+        let got: Vec<Reference> = Reference::parse_all("John 3:16; 4:1-2", Lookup::default()).unwrap();
+        let expect: Vec<Reference> = vec![
+            Reference {
+                book: Book::John,
+                chapter: 3,
+                verses: vec![VerseRange { start: 16, end: 16 }],
+            },
+            Reference {
+                book: Book::John,
+                chapter: 4,
+                verses: vec![VerseRange { start: 1, end: 2 }],
+            },
+        ];
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn t_validate() {
+        let validate = |scripture: &str| Reference::parse(scripture).unwrap().validate();
+        assert_eq!(validate("Psalms 119:176"), Ok(()));
+        assert_eq!(validate("Ps 23:"), Ok(()));
+        assert_eq!(
+            validate("Jude 2:1"),
+            Err(BibleError::ChapterNotFound("Jude".to_string(), 2))
+        );
+        assert_eq!(
+            validate("Ps 0:1"),
+            Err(BibleError::ChapterNotFound("Psalms".to_string(), 0))
+        );
+        assert_eq!(
+            validate("John 3:0"),
+            Err(BibleError::VerseNotFound("John".to_string(), 3, 0))
+        );
+        assert_eq!(
+            validate("John 3:16-12"),
+            Err(BibleError::InvalidRange("John 3:16-12".to_string()))
+        );
+    }
+
+    #[test]
+    fn t_display() {
+        let reference: Reference = Reference::parse("ps 23:").unwrap();
+        assert_eq!(reference.to_string(), "Psalms 23");
+        assert_eq!(reference.display(Style::Nwt).to_string(), "Ps 23");
+
+        let reference: Reference = Reference::parse("Joh 3:16—18, 20").unwrap();
+        assert_eq!(reference.display(Style::Sbl).to_string(), "John 3:16–18, 20");
+    }
+
+    #[test]
+    fn t_parse_invalid_book() {
+        let got = Reference::parse("Mary 3:16");
+        assert_eq!(got.unwrap_err(), BibleError::BookNotFound("Mary".to_string()));
+    }
+}
@@ -0,0 +1,291 @@
+//! A single pass scanner for _potential_ scriptures.
+//!
+//! A scripture is the name of the book or letter, the chapter, and optionally the verses. A single word book
+//! name is found as by the following regular expression, with `\d` limited to ASCII digits. The name is made of
+//! chars with the Unicode `Alphabetic` property, see [`char::is_alphabetic`], which is wider than `\p{L}`:
+//!
+//! ```text
+//! (?<book>(?:[1234]\s?)?\p{Alphabetic}+)\s*(?<chapter>\d+)(?::(?<verse>\d+(?:[—–-]\d+)?(?:,\s*\d+(?:[—–-]\d+)?)*(?:;\s*\d+(?::\d+(?:[—–-]\d+)?(?:,\s*\d+(?:[—–-]\d+)?)*))*)?)
+//! ```
+//!
+//! Book names may be written in any script, e.g. `Génesis`, `Исаия` or `Ἰωάννης`. Whether the book
+//! exists is not checked here, that is done by looking the name up with `Book::try_from`.
+//!
+//! The automaton of the known names, see [`Names`], is only used to extend the name in front of the chapter to
+//! a known name of more than one word, `Song of Solomon`, so there is no regular expression equivalent for those.
+
+use crate::locales::{matcher::Names, nwt_en::MATCHER};
+
+/// _Candidate_ is a potential scripture, all of its fields are byte ranges into the scanned text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Candidate {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) book: (usize, usize),
+    pub(crate) chapter: (usize, usize),
+    pub(crate) verse: Option<(usize, usize)>,
+}
+
+/// _Scanner_ lazily finds the candidates in a string from left to right.
+#[derive(Debug, Clone)]
+pub(crate) struct Scanner<'a> {
+    text: &'a str,
+    pos: usize,
+    /// The known book names, found as the scanner moves along the text.
+    names: Names<'a>,
+}
+
+/// Returns a lazy iterator over the candidates found in `text`.
+pub(crate) fn scan(text: &str) -> Scanner<'_> {
+    Scanner {
+        text,
+        pos: 0,
+        names: MATCHER.names(text),
+    }
+}
+
+fn is_dash(ch: char) -> bool {
+    matches!(ch, '-' | '–' | '—')
+}
+
+impl<'a> Scanner<'a> {
+    fn char_at(&self, idx: usize) -> Option<char> {
+        self.text[idx..].chars().next()
+    }
+
+    /// Returns the index after the run of chars starting at `idx` that satisfy `pred`.
+    fn run_end(&self, idx: usize, pred: impl Fn(char) -> bool) -> usize {
+        let bytes: &[u8] = self.text.as_bytes();
+        let mut end: usize = idx;
+
+        // Most text is ASCII, so only decode a char when a multi-byte one is found.
+        while end < bytes.len() {
+            let ch: char = match bytes[end] {
+                byte if byte.is_ascii() => char::from(byte),
+                _ => match self.char_at(end) {
+                    Some(ch) => ch,
+                    None => break,
+                },
+            };
+            if !pred(ch) {
+                break;
+            }
+            end += ch.len_utf8();
+        }
+
+        end
+    }
+
+    /// Returns the index after `ch` if it is found at `idx`.
+    fn expect(&self, idx: usize, ch: char) -> Option<usize> {
+        (self.char_at(idx) == Some(ch)).then(|| idx + ch.len_utf8())
+    }
+
+    fn digits(&self, idx: usize) -> Option<usize> {
+        let end: usize = self.run_end(idx, |ch| ch.is_ascii_digit());
+        (end > idx).then_some(end)
+    }
+
+    /// A verse or range of verses, `16` or `16-18`.
+    fn range(&self, idx: usize) -> Option<usize> {
+        let end: usize = self.digits(idx)?;
+        let last: Option<usize> = self
+            .char_at(end)
+            .filter(|ch| is_dash(*ch))
+            .and_then(|ch| self.digits(end + ch.len_utf8()));
+        Some(last.unwrap_or(end))
+    }
+
+    /// Any number of `, 17` or `, 17-20` following a range.
+    fn more_ranges(&self, mut idx: usize) -> usize {
+        while let Some(end) = self
+            .expect(idx, ',')
+            .and_then(|after| self.range(self.run_end(after, char::is_whitespace)))
+        {
+            idx = end;
+        }
+        idx
+    }
+
+    /// A verse list continuing into another chapter, `; 4:1, 3`.
+    fn chapter_segment(&self, idx: usize) -> Option<usize> {
+        let after: usize = self.expect(idx, ';')?;
+        let chapter: usize = self.digits(self.run_end(after, char::is_whitespace))?;
+        let verse: usize = self.expect(chapter, ':')?;
+        Some(self.more_ranges(self.range(verse)?))
+    }
+
+    /// Everything after the book name, where `book` is the range of the book name.
+    fn tail(&self, book: (usize, usize)) -> Option<Candidate> {
+        let chapter_start: usize = self.run_end(book.1, char::is_whitespace);
+        let chapter_end: usize = self.digits(chapter_start)?;
+        let verse_start: usize = self.expect(chapter_end, ':')?;
+
+        let verse_end: Option<usize> = self.range(verse_start).map(|mut end| {
+            end = self.more_ranges(end);
+            while let Some(next) = self.chapter_segment(end) {
+                end = next;
+            }
+            end
+        });
+
+        Some(Candidate {
+            start: book.0,
+            end: verse_end.unwrap_or(verse_start),
+            book,
+            chapter: (chapter_start, chapter_end),
+            verse: verse_end.map(|end| (verse_start, end)),
+        })
+    }
+
+    /// Returns the char that ends at `idx`, never looking before where the scanner resumed.
+    fn char_before(&self, idx: usize) -> Option<char> {
+        match idx > self.pos {
+            true => self.text[..idx].chars().next_back(),
+            false => None,
+        }
+    }
+
+    /// Returns the index where the run of chars ending at `idx` that satisfy `pred` starts.
+    fn run_start(&self, idx: usize, pred: impl Fn(char) -> bool) -> usize {
+        let mut start: usize = idx;
+        while let Some(ch) = self.char_before(start).filter(|ch| pred(*ch)) {
+            start -= ch.len_utf8();
+        }
+        start
+    }
+
+    /// Finds the book name in front of the chapter that ends at `colon`, walking backwards.
+    fn book_before(&mut self, colon: usize) -> Option<(usize, usize)> {
+        let chapter: usize = self.run_start(colon, |ch| ch.is_ascii_digit());
+        let name_end: usize = self.run_start(chapter, char::is_whitespace);
+        // Alphabetic also covers letter numbers and marks, such as Roman numerals, which `\p{L}` does not.
+        let name: usize = self.run_start(name_end, char::is_alphabetic);
+        if chapter == colon || name == name_end {
+            return None;
+        }
+
+        // The number of the letter, `1 John` or `1Jo`, with at most one whitespace before the name.
+        let is_number = |ch: char| matches!(ch, '1'..='4');
+        let start: usize = match self.char_before(name) {
+            Some(ch) if is_number(ch) => name - 1,
+            Some(ch) if ch.is_whitespace() => {
+                let space: usize = name - ch.len_utf8();
+                match self.char_before(space) {
+                    Some(ch) if is_number(ch) => space - 1,
+                    _ => name,
+                }
+            }
+            _ => name,
+        };
+
+        // A known name that starts before the last word, `Song of Solomon`, is taken as a whole.
+        let (text, pos) = (self.text, self.pos);
+        let known: Option<usize> = self.names.ending_at(name_end, |known| {
+            known >= pos && !text[..known].ends_with(char::is_alphanumeric)
+        });
+
+        Some((known.map_or(start, |known| known.min(start)), name_end))
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = Candidate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Every scripture has a `:` right after the chapter and there is no other `:` before it, so the
+        // first `:` with a book and chapter in front of it is always the leftmost scripture.
+        while let Some(offset) = self.text[self.pos..].find(':') {
+            let colon: usize = self.pos + offset;
+
+            match self.book_before(colon).and_then(|book| self.tail(book)) {
+                Some(found) => {
+                    self.pos = found.end;
+                    return Some(found);
+                }
+                None => self.pos = colon + 1,
+            }
+        }
+
+        self.pos = self.text.len();
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use regex::Regex;
+
+    /// The regular expression the scanner replaces, kept to check that both agree.
+    fn oracle(text: &str) -> Vec<Candidate> {
+        let re: Regex = Regex::new(r"(?<book>(?:[1234]\s?)?\p{Alphabetic}+)\s*(?<chapter>[0-9]+)(?::(?<verse>[0-9]+(?:[—–-][0-9]+)?(?:,\s*[0-9]+(?:[—–-][0-9]+)?)*(?:;\s*[0-9]+(?::[0-9]+(?:[—–-][0-9]+)?(?:,\s*[0-9]+(?:[—–-][0-9]+)?)*))*)?)").unwrap();
+        re.captures_iter(text)
+            .map(|caps| {
+                let all = caps.get(0).unwrap();
+                let range = |name: &str| caps.name(name).map(|m| (m.start(), m.end()));
+                Candidate {
+                    start: all.start(),
+                    end: all.end(),
+                    book: range("book").unwrap(),
+                    chapter: range("chapter").unwrap(),
+                    verse: range("verse"),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn t_same_as_regex() {
+        let texts: [&str; 21] = [
+            "A popular scripture is John 3:16.",
+            "John 3:16, Mathew 24:14, and Psalms 83:18 are commonly used.",
+            "Other popular scriptures include John 3:16, 17, Matthew 24:14-16, and Psalm 83:18, 17-20.",
+            "1 John 4:8 and 2Ti 3:16 and 3 jo 4: and 21 John 3:16 and 41Co 1:1",
+            "John 3:16; 4:1-2, 5; 6 and Re 21:3—4; 22: and Ge 1:1–3,",
+            "1\nJohn 3:16 and 1 \n John 3:16 and 1  John 3:16",
+            "xJohn 3:16 and 3:16 and John:16 and John 3 and John3:16-",
+            "Léase Génesis 1:1, Исаия 53:5 and Ἰωάννης 3:16",
+            "Chapter 12: verse 3 and Ps 23:1,, 2 and Ps 23:1, x",
+            "Mary 2:23 1 2 3 4 5: 1Jo1:1 1 1:1",
+            "4 4 4 John 3:16:17 and John 3:16;; 4:1",
+            "",
+            "No scriptures here at all.",
+            "ends with John 3",
+            "ab1 John 3:16 and x1:5 John and 2 1 John 3:16",
+            "12:30 John 3:16abc 4:5 and 1John 3:16",
+            "1\u{a0}John 3:16 and Ge\u{a0}\u{a0}1:1 and 4\u{a0}\u{a0}Jo 1:1",
+            "John 3:16:17:18 Re 1:1:",
+            ":::: John ::: 3:16 :",
+            "Génesis 1:1 ÉXODO 2:3 1 Ηλίας 4:5",
+            "Ⅻ 3:16 and 2 Ⅻx 1:1",
+        ];
+
+        for text in texts {
+            assert_eq!(
+                scan(text).collect::<Vec<Candidate>>(),
+                oracle(text),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn t_multi_word_names() {
+        let text: &str = "Song of Solomon 2:1, SONG OF SOLOMON 8:6 and xSong of Solomon 1:1";
+        let got: Vec<&str> = scan(text)
+            .map(|found| &text[found.book.0..found.book.1])
+            .collect();
+        assert_eq!(got, vec!["Song of Solomon", "SONG OF SOLOMON", "Solomon"]);
+    }
+
+    #[test]
+    fn t_candidate_ranges() {
+        let text: &str = "See 1 Corinthians 13:4-7.";
+        let got: Candidate = scan(text).next().unwrap();
+        assert_eq!(&text[got.book.0..got.book.1], "1 Corinthians");
+        assert_eq!(&text[got.chapter.0..got.chapter.1], "13");
+        assert_eq!(got.verse.map(|(start, end)| &text[start..end]), Some("4-7"));
+    }
+}
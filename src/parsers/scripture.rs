@@ -1,18 +1,11 @@
-use crate::locales::{BibleError, BibleRef, nwt_en::Book};
-use lazy_static::lazy_static;
-use regex::Regex;
-
-lazy_static! {
-    /// Regular expresion for capturing scriptures
-    /// Matches the pattern for the name of the book or letter, chapter, and verse.
-    pub static ref RE: regex::Regex =
-                Regex::new(r"(?<book>(?:[1234]\s?)?[a-zA-Z]+)\s*(?<chapter>\d+)(?::(?<verse>\d+(?:[—–-]\d+)?(?:,\s*\d+(?:[—–-]\d+)?)*(?:;\s*\d+(?::\d+(?:[—–-]\d+)?(?:,\s*\d+(?:[—–-]\d+)?)*))*)?)").expect("error while compiling the FIND_BOOK regex in scripture");
-}
+use crate::locales::{BibleError, BibleRef, nwt_en::{Book, Lookup}};
+
+use super::scanner::{Candidate, scan};
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Bible<'a> {
     book: &'a str,
-    booknum: String,
+    booknum: u8,
     chapter: &'a str,
     verse: &'a str,
 }
@@ -25,7 +18,7 @@ impl<'a> Bible<'a> {
             book,
             chapter,
             verse,
-            booknum: Default::default(),
+            booknum: Book::get_index(book).unwrap_or_default(),
         }
     }
 
@@ -42,7 +35,15 @@ impl<'a> Bible<'a> {
     }
 
     pub(crate) fn get_idx(&self) -> Result<u8, BibleError> {
-        Book::get_index(self.book)
+        match self.booknum {
+            0 => Err(BibleError::BookNotFound(self.book.to_string())),
+            idx => Ok(idx),
+        }
+    }
+
+    /// Returns the book that was resolved when the scripture was parsed.
+    pub(crate) fn get_book_enum(&self) -> Result<Book, BibleError> {
+        Book::try_from(self.get_idx()?)
     }
 
     pub(crate) fn is_range(&self) -> bool {
@@ -50,29 +51,28 @@ impl<'a> Bible<'a> {
     }
 
     pub(crate) fn parse(scripture: &'a str) -> Result<Bible<'a>, BibleError> {
-        let caps = RE
-            .captures(scripture)
+        Self::parse_with(scripture, Lookup::default())
+    }
+
+    /// Parses a scripture, looking up the book name as set by `lookup`, i.e. ignoring diacritics so `Génesis`
+    /// matches `Genesis`.
+    pub(crate) fn parse_with(scripture: &'a str, lookup: Lookup) -> Result<Bible<'a>, BibleError> {
+        let found: Candidate = scan(scripture)
+            .next()
             .ok_or_else(|| BibleError::ParsingError(scripture.to_string()))?;
 
-        let book_name = caps
-            .name("book")
-            .ok_or_else(|| BibleError::ParsingError(scripture.to_string()))?
-            .as_str();
+        let book_name: &str = &scripture[found.book.0..found.book.1];
 
-        if !Book::is_valid(book_name) {
-            return Err(BibleError::BookNotFound(book_name.to_string()));
-        }
+        let book: Book = lookup.book(book_name).map_err(BibleError::BookNotFound)?;
 
-        let booknum = Book::get_index(book_name)?.to_string();
-        let chapter = caps
-            .name("chapter")
-            .ok_or_else(|| BibleError::ParsingError(scripture.to_string()))?
-            .as_str();
-        let verse = caps.name("verse").map(|m| m.as_str()).unwrap_or_default();
+        let chapter: &str = &scripture[found.chapter.0..found.chapter.1];
+        let verse: &str = found
+            .verse
+            .map_or("", |(start, end)| &scripture[start..end]);
 
         Ok(Self {
             book: book_name,
-            booknum,
+            booknum: book as u8,
             chapter,
             verse,
         })
@@ -103,7 +103,7 @@ mod test {
             book: "John",
             chapter: "3",
             verse: "16",
-            booknum: "43".into(),
+            booknum: 43,
         };
         let result: Bible = Bible::parse(input).unwrap();
         assert_eq!(result, expect);
@@ -116,7 +116,7 @@ mod test {
             book: "1 Timothy",
             chapter: "3",
             verse: "16",
-            booknum: "54".into(),
+            booknum: 54,
         };
         let result: Bible = Bible::parse(input).unwrap();
         assert_eq!(result, expect);
@@ -129,9 +129,76 @@ mod test {
             book: "1 Timothy",
             chapter: "3",
             verse: "16-20",
-            booknum: "54".into(),
+            booknum: 54,
+        };
+        let result: Bible = Bible::parse(input).unwrap();
+        assert_eq!(result, expect);
+    }
+
+    #[test]
+    fn t_find_book_all_caps() {
+        let input: &str = "1 CORINTHIANS 13:4-7";
+        let expect: Bible = Bible {
+            book: "1 CORINTHIANS",
+            chapter: "13",
+            verse: "4-7",
+            booknum: 46,
         };
         let result: Bible = Bible::parse(input).unwrap();
         assert_eq!(result, expect);
     }
+
+    #[test]
+    fn t_find_book_diacritics() {
+        let input: &str = "Génesis 1:1";
+        let got = Bible::parse(input);
+        assert_eq!(
+            got.unwrap_err(),
+            BibleError::BookNotFound("Génesis".to_string())
+        );
+
+        let lookup: Lookup = Lookup {
+            ignore_diacritics: true,
+            ..Lookup::default()
+        };
+        let result: Bible = Bible::parse_with(input, lookup).unwrap();
+        assert_eq!(result.get_book(), "Génesis");
+        assert_eq!(result.get_idx(), Ok(1));
+    }
+
+    #[test]
+    fn t_find_book_usfm() {
+        let input: &str = "JHN 3:16";
+        assert!(Bible::parse(input).is_err());
+
+        let lookup: Lookup = Lookup {
+            usfm: true,
+            ..Lookup::default()
+        };
+        assert_eq!(Bible::parse_with(input, lookup).unwrap().get_idx(), Ok(43));
+        assert_eq!(Bible::parse_with("Joh 3:16", lookup).unwrap().get_idx(), Ok(43));
+    }
+
+    #[test]
+    fn t_find_book_sbl() {
+        let input: &str = "Rom 8:28";
+        assert!(Bible::parse(input).is_err());
+
+        let lookup: Lookup = Lookup {
+            sbl: true,
+            ..Lookup::default()
+        };
+        assert_eq!(Bible::parse_with(input, lookup).unwrap().get_idx(), Ok(45));
+        assert_eq!(Bible::parse_with("1 Thess 4:13", lookup).unwrap().get_idx(), Ok(52));
+        assert_eq!(Bible::parse_with("Ro 8:28", lookup).unwrap().get_idx(), Ok(45));
+    }
+
+    #[test]
+    fn t_non_latin_book() {
+        let got = Bible::parse("Исаия 53:5");
+        assert_eq!(
+            got.unwrap_err(),
+            BibleError::BookNotFound("Исаия".to_string())
+        );
+    }
 }
@@ -0,0 +1,157 @@
+use std::io::{self, Read, Write};
+
+use crate::locales::BibleError;
+
+use super::{link, scanner::scan};
+
+/// The number of bytes requested from the reader at a time.
+const CHUNK: usize = 8 * 1024;
+/// The number of bytes held back from the end of the buffer in case a scripture continues in the next read.
+/// Scriptures longer than this may be missed when they straddle two reads.
+const WINDOW: usize = 1024;
+/// When a single scripture grows past this size the buffer is processed as is to keep memory bounded.
+const MAX_PENDING: usize = 64 * 1024;
+
+/// Reads `reader` to the end, applying `transform` to the text and writing the result to `writer`.
+/// Text is handed to `transform` in segments that are cut where no scripture can straddle them, so the
+/// output is the same as transforming the whole text at once while only a few kilobytes are held in memory.
+pub(crate) fn transform<R, W, F>(mut reader: R, mut writer: W, mut transform: F) -> io::Result<()>
+where
+    R: Read,
+    W: Write,
+    F: FnMut(&str) -> Result<String, BibleError>,
+{
+    let mut chunk: Vec<u8> = vec![0; CHUNK];
+    let mut pending: Vec<u8> = Vec::with_capacity(CHUNK + WINDOW);
+
+    loop {
+        let read: usize = match reader.read(&mut chunk) {
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        pending.extend_from_slice(&chunk[..read]);
+
+        let eof: bool = read == 0;
+        let text: &str = valid_utf8(&pending, eof)?;
+        let commit: usize = match eof {
+            true => text.len(),
+            false => commit_point(text),
+        };
+
+        if commit > 0 {
+            let output: String = transform(&text[..commit])
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            writer.write_all(output.as_bytes())?;
+            pending.drain(..commit);
+        }
+
+        if eof {
+            return writer.flush();
+        }
+    }
+}
+
+/// Returns the longest valid UTF-8 prefix of `bytes`. A char cut in half by the last read is left for the
+/// next read, any other invalid byte is an error.
+fn valid_utf8(bytes: &[u8], eof: bool) -> io::Result<&str> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok(text),
+        Err(e) if e.error_len().is_none() && !eof => std::str::from_utf8(&bytes[..e.valid_up_to()])
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+    }
+}
+
+/// Returns the index up to which `text` can be processed without cutting through a scripture that might
+/// continue in the next read.
+fn commit_point(text: &str) -> usize {
+    if text.len() <= WINDOW {
+        return 0;
+    }
+
+    let mut limit: usize = text.len() - WINDOW;
+    while !text.is_char_boundary(limit) {
+        limit -= 1;
+    }
+
+    // A scripture, or the link around it, that runs into the held back window is kept whole for the next round.
+    let commit: usize = scan(text)
+        .map(|found| link::extent(text, &(found.start, found.end)))
+        .find(|found| found.1 > limit)
+        .map_or(limit, |found| found.0.min(limit));
+
+    match commit == 0 && text.len() >= MAX_PENDING {
+        true => text.len(),
+        false => commit,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::locales::nwt_en::Site;
+    use crate::parsers::surround::Script;
+    use pretty_assertions::assert_eq;
+
+    /// A reader that returns at most `step` bytes per read to force scriptures across buffer boundaries.
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len: usize = self.step.min(buf.len()).min(self.bytes.len());
+            buf[..len].copy_from_slice(&self.bytes[..len]);
+            self.bytes = &self.bytes[len..];
+            Ok(len)
+        }
+    }
+
+    fn surround(text: &str) -> Result<String, BibleError> {
+        Ok(Script::new(text).prefix("<").postfix(">").surround().get_text())
+    }
+
+    #[test]
+    fn t_same_as_whole_text() {
+        let text: String = "Léase Génesis 1:1, then John 3:16, 17 and 1 Corinthians 13:4-7. ".repeat(500);
+
+        for step in [1, 7, 1000, CHUNK] {
+            let mut output: Vec<u8> = Vec::new();
+            let reader = Trickle { bytes: text.as_bytes(), step };
+            transform(reader, &mut output, surround).unwrap();
+            assert_eq!(String::from_utf8(output).unwrap(), surround(&text).unwrap());
+        }
+    }
+
+    #[test]
+    fn t_scripture_across_chunks() {
+        // Place the scripture right on the boundary of the first read.
+        let mut text: String = "x".repeat(CHUNK - 5);
+        text.push_str(" John 3:16, 17 is split.");
+
+        let mut output: Vec<u8> = Vec::new();
+        transform(text.as_bytes(), &mut output, surround).unwrap();
+        assert!(String::from_utf8(output).unwrap().ends_with(" <John 3:16, 17> is split."));
+    }
+
+    #[test]
+    fn t_link_across_chunks() {
+        // The scripture ends before the held back window, the link around it does not.
+        let mut text: String = "x".repeat(CHUNK - WINDOW - 12);
+        text.push_str(" [John 3:16](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016) again.");
+        text.push_str(&"y".repeat(CHUNK));
+
+        let mut output: Vec<u8> = Vec::new();
+        transform(text.as_bytes(), &mut output, |text| crate::url(&Site::JwOrg, text)).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), text);
+    }
+
+    #[test]
+    fn t_invalid_utf8() {
+        let bytes: &[u8] = b"John 3:16 \xff";
+        let got = transform(bytes, Vec::new(), surround);
+        assert_eq!(got.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
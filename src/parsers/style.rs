@@ -0,0 +1,184 @@
+use std::fmt;
+
+use crate::locales::nwt_en::Book;
+
+use super::reference::{Reference, VerseRange};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// _Style_ is how a scripture is written by [`Reference::display`], [`crate::Script::style`] and
+/// [`crate::normalize`].
+///
+/// Apart from OSIS, every style writes the chapter and verses the same way, `John 3:16, 18-20; 4:1`: the verses
+/// of a chapter are separated by `, `, the chapters by `; ` and a range by a dash with no spaces around it.
+///
+/// ## Example
+/// ```
+/// use est::Style;
+///
+/// assert_eq!(est::normalize("Read joh 3:16—18.", Style::Full).unwrap(), "Read John 3:16-18.");
+/// assert_eq!(est::normalize("Read 1 corinthians 13:4-7,13.", Style::Sbl).unwrap(), "Read 1 Cor 13:4–7, 13.");
+/// ```
+pub enum Style {
+    /// The name of the book in the NWT, `1 Corinthians 13:4-7`.
+    #[default]
+    Full,
+    /// The abbreviation of the book in the NWT, `1Co 13:4-7`.
+    Nwt,
+    /// The abbreviation of the book in the SBL Handbook of Style, with an en dash for ranges, `1 Cor 13:4–7`,
+    /// detected again with [`crate::Script::sbl`].
+    Sbl,
+    /// The abbreviation of the book in the NWT without spaces between verses and chapters, `1Co 13:4-7,13;14:1`.
+    Compact,
+    /// OSIS references, `1Cor.13.4-1Cor.13.7 1Cor.13.13`, as used by Bible datasets.
+    Osis,
+    /// The USFM/Paratext code of the book, `1CO 13:4-7`, detected again with [`crate::Script::usfm`].
+    Usfm,
+}
+
+impl Style {
+    /// Returns the name of `book` in this style.
+    pub fn book(&self, book: Book) -> &'static str {
+        match self {
+            Style::Full => book.canonical_name(),
+            Style::Nwt | Style::Compact => book.abbreviation(),
+            Style::Sbl => book.sbl_abbreviation(),
+            Style::Osis => book.osis(),
+            Style::Usfm => book.usfm(),
+        }
+    }
+
+    /// The dash between the first and last verse of a range.
+    fn dash(&self) -> char {
+        match self {
+            Style::Sbl => '–',
+            Style::Full | Style::Nwt | Style::Compact | Style::Osis | Style::Usfm => '-',
+        }
+    }
+
+    /// The separators between the verses of a chapter and between chapters.
+    fn separators(&self) -> (&'static str, &'static str) {
+        match self {
+            Style::Full | Style::Nwt | Style::Sbl | Style::Usfm => (", ", "; "),
+            Style::Compact => (",", ";"),
+            Style::Osis => (" ", " "),
+        }
+    }
+
+    /// Pushes `references`, the chapters of a single scripture found in a text, onto `output`. A chapter
+    /// without verses keeps the colon it was written with, `Ps 23:`, so it is still found when scanned again.
+    pub(crate) fn push(&self, output: &mut String, references: &[Reference]) {
+        self.write(output, references)
+            .expect("writing to a String does not fail");
+
+        if *self != Style::Osis && references.last().is_some_and(|last| last.verses.is_empty()) {
+            output.push(':');
+        }
+    }
+
+    /// Writes `references`, the chapters of a single scripture, onto `f`.
+    fn write<W: fmt::Write>(&self, f: &mut W, references: &[Reference]) -> fmt::Result {
+        let (verse_separator, chapter_separator) = self.separators();
+
+        for (i, reference) in references.iter().enumerate() {
+            match (i, self) {
+                (0, Style::Osis) => {}
+                (0, _) => write!(f, "{} ", self.book(reference.book))?,
+                _ => f.write_str(chapter_separator)?,
+            }
+
+            if *self == Style::Osis {
+                self.write_osis(f, reference)?;
+                continue;
+            }
+
+            write!(f, "{}", reference.chapter)?;
+            if !reference.verses.is_empty() {
+                f.write_char(':')?;
+            }
+            for (j, verses) in reference.verses.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(verse_separator)?;
+                }
+                write!(f, "{}", verses.start)?;
+                if verses.end != verses.start {
+                    write!(f, "{}{}", self.dash(), verses.end)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes `reference` as space separated OSIS references, a range being written as `John.3.16-John.3.18`.
+    fn write_osis<W: fmt::Write>(&self, f: &mut W, reference: &Reference) -> fmt::Result {
+        let book: &str = self.book(reference.book);
+        let chapter: u16 = reference.chapter;
+
+        if reference.verses.is_empty() {
+            return write!(f, "{book}.{chapter}");
+        }
+        for (j, VerseRange { start, end }) in reference.verses.iter().enumerate() {
+            if j > 0 {
+                f.write_char(' ')?;
+            }
+            write!(f, "{book}.{chapter}.{start}")?;
+            if end != start {
+                write!(f, "-{book}.{chapter}.{end}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// _Styled_ writes a [`Reference`] in a [`Style`], see [`Reference::display`].
+#[derive(Debug, Clone, Copy)]
+pub struct Styled<'a> {
+    pub(crate) reference: &'a Reference,
+    pub(crate) style: Style,
+}
+
+impl fmt::Display for Styled<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.style.write(f, std::slice::from_ref(self.reference))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::locales::nwt_en::Lookup;
+    use pretty_assertions::assert_eq;
+
+    fn render(style: Style, scripture: &str) -> String {
+        let mut output: String = String::new();
+        style.push(
+            &mut output,
+            &Reference::parse_all(scripture, Lookup::default()).unwrap(),
+        );
+        output
+    }
+
+    #[test]
+    fn t_styles() {
+        let scripture: &str = "CA 2:1–3,5;  3:4";
+        assert_eq!(
+            render(Style::Full, scripture),
+            "Song of Solomon 2:1-3, 5; 3:4"
+        );
+        assert_eq!(render(Style::Nwt, scripture), "Ca 2:1-3, 5; 3:4");
+        assert_eq!(render(Style::Sbl, scripture), "Song 2:1–3, 5; 3:4");
+        assert_eq!(render(Style::Compact, scripture), "Ca 2:1-3,5;3:4");
+        assert_eq!(render(Style::Usfm, scripture), "SNG 2:1-3, 5; 3:4");
+        assert_eq!(
+            render(Style::Osis, scripture),
+            "Song.2.1-Song.2.3 Song.2.5 Song.3.4"
+        );
+    }
+
+    #[test]
+    fn t_no_verse() {
+        assert_eq!(render(Style::Nwt, "Psalms 23:"), "Ps 23:");
+        assert_eq!(render(Style::Osis, "Psalms 23:"), "Ps.23");
+    }
+}
@@ -1,12 +1,27 @@
-use std::borrow::{Borrow, Cow};
-use crate::parsers::scripture::RE;
+use std::borrow::Cow;
+use crate::parsers::scanner::scan;
 
 use crate::{
-    locales::{nwt_en::Site, BibleError},
+    locales::{
+        nwt_en::{Lookup, Site},
+        BibleError,
+    },
     url::Url,
 };
 
+use super::anchor::{escape, Anchor};
+use super::format::LinkFormat;
+use super::link::{self, Link};
+use super::{html, markdown};
+use super::matches::References;
+use super::position::{self, Span};
+use super::reference::Reference;
 use super::scripture::Bible;
+use super::style::Style;
+use super::wiki::WikiLink;
+
+/// The room reserved for the markup and URL of each scripture when linking.
+const URL_CAPACITY: usize = 96;
 
 /// _ScriptSlice_ type describes as a tuple the begining and ending index plus one for a scripture found in a string.
 ///
@@ -18,6 +33,12 @@ pub type ScriptSlice = (Start, End);
 type Start = usize;
 type End = usize;
 
+/// True if `slice` lies entirely within one of the `ranges`, which must be in order.
+pub(crate) fn contains(ranges: &[ScriptSlice], slice: &ScriptSlice) -> bool {
+    let idx: usize = ranges.partition_point(|range| range.1 < slice.1);
+    ranges.get(idx).is_some_and(|range| range.0 <= slice.0)
+}
+
 /// The _ScriptureCollection_ is a vector contain scruptures. TODO: Fix docs
 pub type ScriptureCollection = Vec<String>;
 
@@ -38,6 +59,7 @@ struct Elements<'a> {
     postfix_len: Option<usize>,
 }
 #[derive(Debug,PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// _Locations_ contains the start and end indexes of all the scriptures found in the string.
 pub struct Locations{
     /// The start and end indexes of all the scriptures found in the string passed in.
@@ -46,43 +68,91 @@ pub struct Locations{
     pub string: String,
 }
 
+impl Locations {
+    /// Returns the location of each scripture as a [`Span`] holding byte, char, UTF-16 and line:column offsets.
+    /// The column is also given in UTF-16 code units, the unit editors and language servers use.
+    ///
+    /// ## Example
+    /// ```
+    /// let locations = est::get_locations("Léase\nJohn 3:16");
+    /// let span = locations.spans()[0];
+    /// assert_eq!((span.start.byte, span.start.char, span.start.utf16), (7, 6, 6));
+    /// assert_eq!((span.start.line, span.start.column), (2, 1));
+    /// ```
+    pub fn spans(&self) -> Vec<Span> {
+        position::spans(&self.string, &self.slices)
+    }
+}
+
 
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 /// The _Script_ struct describes properties needed for sucessfully wrapping a scripture with _elements_.
+///
+/// The free functions such as `est::surround` and `est::url` cover the common cases, `Script` can be used
+/// directly when additional options are needed.
+///
+/// ## Example
+/// ```
+/// use est::Script;
+///
+/// let text: &str = "Léase GÉNESIS 1:1.";
+/// let got: String = Script::new(text)
+///     .prefix("*")
+///     .postfix("*")
+///     .ignore_diacritics()
+///     .normalize()
+///     .surround()
+///     .get_text();
+/// assert_eq!("Léase *Genesis 1:1*.", got);
+/// ```
 pub struct Script<'a> {
     /// The _elements_ that will wrap around the scripture. i.e. the _prefix_ and _postfix_.
     elements: Elements<'a>,
     /// Both the start and end (+1) index for the scripture(s) within the string to be able to locate where the scriptures are found within the string.
     slices: Vec<ScriptSlice>,
     /// The full string that was passed into the library that contains the scripture.
-    string: String,
+    /// It is borrowed until `surround()` or `url()` build the modified string.
+    string: Cow<'a, str>,
+    /// When `true`, book names are rewritten to their canonical form, i.e. `JOHN 3:16` becomes `John 3:16`.
+    normalize: bool,
+    /// When set, each valid scripture is rewritten in this style, i.e. `joh 3:16 —18` becomes `John 3:16-18`.
+    style: Option<Style>,
+    /// How book names are looked up, i.e. ignoring diacritics so `Génesis` matches `Genesis`.
+    lookup: Lookup,
+    /// The markup used to link scriptures.
+    link_format: LinkFormat,
+    /// The prose of the string when it is treated as a Markdown or HTML document, scriptures outside of it are ignored.
+    prose: Option<Vec<ScriptSlice>>,
+    /// The scriptures within the text of Markdown links, left out of the prose but refreshed by `url()`.
+    linked: Vec<ScriptSlice>,
 }
 
 #[allow(unused_variables)]
 impl<'a> Script<'a> {
     /// Find all _potential_ scriptures in a string and return the beginning index and length.
-    /// Will accept `&str` or `String` types.
-    pub(crate) fn new<S>(text: S) -> Self
+    /// Will accept `&str` or `String` types, a `&str` is borrowed and not copied.
+    pub fn new<S>(text: S) -> Self
     where
-        S: Into<String> + Clone,
+        S: Into<Cow<'a, str>>,
     {
-        let re: &regex::Regex = &RE;
-
-        let text_to_str: Cow<str> = Cow::from(text.clone().into());
-        let matches = re.find_iter(Cow::borrow(&text_to_str));
-
-        let mut scrip_slices: Vec<ScriptSlice> = Vec::new();
-        for script in matches {
-            scrip_slices.push((script.start(), script.end()));
-        }
+        let string: Cow<'a, str> = text.into();
+        let scrip_slices: Vec<ScriptSlice> = scan(&string)
+            .map(|script| (script.start, script.end))
+            .collect();
 
         Self {
-            string: text.into(),
+            string,
             slices: scrip_slices,
             elements: Elements {
                 ..Default::default()
             },
+            normalize: false,
+            style: None,
+            lookup: Lookup::default(),
+            link_format: LinkFormat::Markdown,
+            prose: None,
+            linked: Vec::new(),
         }
     }
 
@@ -98,6 +168,115 @@ impl<'a> Script<'a> {
         self
     }
 
+    /// Rewrites the book name of each valid scripture to its canonical form when `surround()` or `url()` is called.
+    pub fn normalize(mut self) -> Self {
+        self.normalize = true;
+        self
+    }
+
+    /// Rewrites each valid scripture in `style` when `surround()`, `url()`, `html()` or `wiki()` is called.
+    /// Unlike [`Script::normalize`], the whole scripture is rewritten, dashes and spacing included.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::{Script, Style};
+    ///
+    /// let got: String = Script::new("Read 1 corinthians 13:4–7,13.").style(Style::Nwt).surround().get_text();
+    /// assert_eq!("Read 1Co 13:4-7, 13.", got);
+    /// ```
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Ignores diacritics when looking up book names, so `Génesis 1:1` resolves to Genesis.
+    pub fn ignore_diacritics(mut self) -> Self {
+        self.lookup.ignore_diacritics = true;
+        self
+    }
+
+    /// Also detects the USFM/Paratext codes of the books, such as `JHN` and `1CO`, as book names.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::Script;
+    ///
+    /// let got: String = Script::new("Read JHN 3:16 and 1CO 13:4.").usfm().normalize().surround().get_text();
+    /// assert_eq!("Read John 3:16 and 1 Corinthians 13:4.", got);
+    /// ```
+    pub fn usfm(mut self) -> Self {
+        self.lookup.usfm = true;
+        self
+    }
+
+    /// Also detects the abbreviations of the SBL Handbook of Style, such as `Gen` and `Rom`, as book names, so the
+    /// output of [`Style::Sbl`] can be read again.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::{Script, Style};
+    ///
+    /// let got: String = Script::new("Read Rom 8:28 and 1 Thess 4:13.").sbl().style(Style::Nwt).surround().get_text();
+    /// assert_eq!("Read Ro 8:28 and 1Th 4:13.", got);
+    /// ```
+    pub fn sbl(mut self) -> Self {
+        self.lookup.sbl = true;
+        self
+    }
+
+    /// The markup used by `url()` to link each scripture, Markdown by default.
+    pub fn link_format(mut self, link_format: LinkFormat) -> Self {
+        self.link_format = link_format;
+        self
+    }
+
+    /// Treats the text as a CommonMark document, only scriptures in prose are changed or returned.
+    /// Scriptures inside code, links, images, HTML and front matter are left untouched, except that `url()`
+    /// still refreshes links to one of the supported sites.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::Script;
+    ///
+    /// let text: &str = "Read John 3:16, not `Ge 1:1`.";
+    /// let got: String = Script::new(text).markdown().prefix("*").postfix("*").surround().get_text();
+    /// assert_eq!("Read *John 3:16*, not `Ge 1:1`.", got);
+    /// ```
+    pub fn markdown(mut self) -> Self {
+        let (prose, links): (Vec<ScriptSlice>, Vec<ScriptSlice>) = markdown::prose(&self.string);
+        self.linked = self
+            .slices
+            .iter()
+            .filter(|slice| contains(&links, slice))
+            .copied()
+            .collect();
+        self.within(prose)
+    }
+
+    /// Treats the text as an HTML document, only scriptures in text nodes are changed or returned.
+    /// Scriptures inside tags and attributes, comments, `<script>`, `<style>`, `<a>`, `<code>` and `<pre>`
+    /// elements are left untouched, as is every byte outside of the scriptures.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::Script;
+    ///
+    /// let text: &str = r#"<p title="John 3:16">Read John 3:16, not <a href="/ge">Ge 1:1</a>.</p>"#;
+    /// let got: String = Script::new(text).html_document().prefix("<b>").postfix("</b>").surround().get_text();
+    /// assert_eq!(r#"<p title="John 3:16">Read <b>John 3:16</b>, not <a href="/ge">Ge 1:1</a>.</p>"#, got);
+    /// ```
+    pub fn html_document(self) -> Self {
+        let text_nodes: Vec<ScriptSlice> = html::text_nodes(&self.string);
+        self.within(text_nodes)
+    }
+
+    /// Keeps only the scriptures that lie within `ranges` of the string.
+    fn within(mut self, ranges: Vec<ScriptSlice>) -> Self {
+        self.slices.retain(|slice| contains(&ranges, slice));
+        self.prose = Some(ranges);
+        self
+    }
+
     #[allow(dead_code)]
     fn is_prefix(&self) -> bool {
         self.elements.prefix.is_some()
@@ -110,64 +289,217 @@ impl<'a> Script<'a> {
 
     /// The surround method adds a prefix and postfix when the corresponding methods are used.
     /// `surround()` does not verify if a captured _scripture_ is valid.
-    pub(crate) fn surround(mut self) -> Self {
-        // .rev method is used to avoid dealing with the changing size of the string.
-        for item in self.slices.iter().rev() {
-            self.string.insert_str(
-                item.0 + (item.1 - item.0),
-                self.elements
-                    .postfix
-                    .map_or("", |postfix_value| postfix_value),
-            );
-
-            self.string.insert_str(
-                item.0,
-                self.elements.prefix.map_or("", |prefix_value| prefix_value),
-            );
+    pub fn surround(mut self) -> Self {
+        let prefix: &str = self.elements.prefix.unwrap_or_default();
+        let postfix: &str = self.elements.postfix.unwrap_or_default();
+
+        // The string is built in a single pass, the capacity covers everything but normalized book names.
+        let mut output: String = String::with_capacity(
+            self.string.len() + self.slices.len() * (prefix.len() + postfix.len()),
+        );
+        let mut last: usize = 0;
+
+        for slice in self.slices.iter() {
+            output.push_str(&self.string[last..slice.0]);
+            output.push_str(prefix);
+            self.push_scripture(&mut output, slice, self.normalize);
+            output.push_str(postfix);
+            last = slice.1;
         }
+        output.push_str(&self.string[last..]);
 
+        self.string = Cow::Owned(output);
         self
     }
 
     /// Returns the original string with URL markup for all scriptures.
-    pub(crate) fn url(mut self, site: &Site) -> Result<Self, BibleError> {
-        // .rev() method is used to avoid dealing with the changing size of the string as new characters are added.
-        for (start, end) in self.slices.iter().rev() {
-            let verse_slice: String = self.get_from_slice(&(*start, *end));
-            let bible: Bible = Bible::parse(verse_slice.as_str())?;
-            let url: String = site.get_url(&bible)?;
+    ///
+    /// Linking is idempotent, a scripture within the text of a link in the [`LinkFormat`] or in Markdown that
+    /// points to one of the supported sites has its URL refreshed in place, and a scripture linked anywhere else is
+    /// left alone, so the same text can be linked again after it changes.
+    pub fn url(mut self, site: &Site) -> Result<Self, BibleError> {
+        let mut output: String =
+            String::with_capacity(self.string.len() + self.slices.len() * URL_CAPACITY);
+        let mut scripture: String = String::new();
+        let mut last: usize = 0;
+        let mut slices: Vec<ScriptSlice> = self.slices.iter().chain(&self.linked).copied().collect();
+        slices.sort_unstable();
+
+        for slice in slices.iter() {
+            let found: Option<Link> = link::around(&self.string, slice, self.link_format);
+            let is_site = |found: &Link| {
+                let url: Cow<str> = found.format.url(&self.string[found.target.0..found.target.1]);
+                Site::ALL.iter().any(|known| known.is_link(&url))
+            };
+            // Scriptures already linked somewhere other than a supported site are left alone, as are the other
+            // scriptures of a link that was just refreshed.
+            if slice.0 < last || found.is_some_and(|found| found.extent.0 < last || !is_site(&found)) {
+                continue;
+            }
+
+            let reference: Reference =
+                Reference::parse_with(self.get_from_slice(slice), self.lookup)?;
+            let url: String = site.get_url(&reference)?;
+
+            match found {
+                Some(found) => {
+                    output.push_str(&self.string[last..found.extent.0]);
+                    self.push_refreshed(&mut output, &found, slice, &url);
+                    last = found.extent.1;
+                }
+                None => {
+                    output.push_str(&self.string[last..slice.0]);
+                    scripture.clear();
+                    self.push_scripture(&mut scripture, slice, self.normalize);
+                    self.link_format.push(&mut output, &url, &scripture);
+                    last = slice.1;
+                }
+            }
+        }
+        output.push_str(&self.string[last..]);
+
+        self.string = Cow::Owned(output);
+        Ok(self)
+    }
 
-            self.string
-                .insert_str(*start + (*end - *start), format!("]({})", url).as_str());
+    /// Returns the original string with an HTML `<a>` element, described by `anchor`, around each scripture.
+    /// The URL and the scripture are HTML escaped, the rest of the string is left as is. Scriptures already inside
+    /// an `<a>` element are left alone, so linking the same string twice gives the same result.
+    pub fn html(mut self, site: &Site, anchor: &Anchor) -> Result<Self, BibleError> {
+        let mut output: String =
+            String::with_capacity(self.string.len() + self.slices.len() * URL_CAPACITY * 2);
+        let mut scripture: String = String::new();
+        let mut data_ref: String = String::new();
+        let mut last: usize = 0;
+        let anchors: Vec<ScriptSlice> = html::anchors(&self.string);
+
+        for slice in self.slices.iter() {
+            if contains(&anchors, slice) {
+                continue;
+            }
+
+            let reference: Reference =
+                Reference::parse_with(self.get_from_slice(slice), self.lookup)?;
+            let url: String = site.get_url(&reference)?;
+
+            scripture.clear();
+            self.push_scripture(&mut scripture, slice, self.normalize);
+            data_ref.clear();
+            self.push_scripture(&mut data_ref, slice, true);
 
-            self.string.insert(*start, '[');
+            output.push_str(&self.string[last..slice.0]);
+            anchor.push_open(&mut output, &url, &data_ref);
+            escape(&mut output, &scripture);
+            output.push_str("</a>");
+            last = slice.1;
         }
+        output.push_str(&self.string[last..]);
 
+        self.string = Cow::Owned(output);
         Ok(self)
     }
 
-    /// Returns the text field of the Script struct.
-    pub(crate) fn get_text(self) -> String {
-        self.string
+    /// Returns the original string with a wiki link, described by `wiki`, around each scripture.
+    /// The target of each link is built from the parsed scripture, scriptures that are already the text of a
+    /// wiki link are left alone.
+    pub fn wiki(mut self, wiki: &WikiLink) -> Result<Self, BibleError> {
+        let mut output: String =
+            String::with_capacity(self.string.len() + self.slices.len() * URL_CAPACITY / 2);
+        let mut scripture: String = String::new();
+        let mut last: usize = 0;
+
+        for slice in self.slices.iter() {
+            if link::in_wiki_link(&self.string, slice) {
+                continue;
+            }
+
+            let reference: Reference =
+                Reference::parse_with(self.get_from_slice(slice), self.lookup)?;
+
+            scripture.clear();
+            self.push_scripture(&mut scripture, slice, self.normalize);
+            output.push_str(&self.string[last..slice.0]);
+            wiki.push(&mut output, &reference, &scripture);
+            last = slice.1;
+        }
+        output.push_str(&self.string[last..]);
+
+        self.string = Cow::Owned(output);
+        Ok(self)
     }
 
-    // Returns a `String` to avoid borrowing headaches
-    pub(crate) fn get_from_slice(&self, slice: &(usize, usize)) -> String {
-        let clone_obj: Script<'a> = self.clone();
-        let text: String = clone_obj.get_text();
+    /// Pushes the link `found` around the scripture at `slice` onto `output`, pointing to `url` and with the
+    /// scripture rewritten if asked to. The target comes before the text in some formats and after it in others.
+    fn push_refreshed(&self, output: &mut String, found: &Link, slice: &ScriptSlice, url: &str) {
+        let mut last: usize = found.extent.0;
+        if found.target.0 < slice.0 {
+            output.push_str(&self.string[last..found.target.0]);
+            found.format.push_url(output, url);
+            last = found.target.1;
+        }
+
+        output.push_str(&self.string[last..slice.0]);
+        self.push_scripture(output, slice, self.normalize);
+        last = slice.1;
 
-        text[slice.0..slice.1].to_owned()
+        if found.target.0 > slice.0 {
+            output.push_str(&self.string[last..found.target.0]);
+            found.format.push_url(output, url);
+            last = found.target.1;
+        }
+        output.push_str(&self.string[last..found.extent.1]);
+    }
+
+    /// Pushes the scripture found within `slice` onto `output`.
+    /// When a style is set the scripture is rewritten in it, otherwise when `normalize` is `true` the book name is
+    /// replaced with its canonical form. Scriptures that cannot be parsed are pushed untouched.
+    fn push_scripture(&self, output: &mut String, slice: &ScriptSlice, normalize: bool) {
+        let scripture: &str = self.get_from_slice(slice);
+
+        if let Some(style) = self.style {
+            match Reference::parse_all(scripture, self.lookup) {
+                Ok(references) => style.push(output, &references),
+                Err(_) => output.push_str(scripture),
+            }
+            return;
+        }
+
+        let book = match normalize {
+            true => scan(scripture).next().map(|found| found.book),
+            false => None,
+        };
+
+        let name = book.and_then(|book| self.lookup.book(&scripture[book.0..book.1]).ok());
+
+        match (book, name) {
+            (Some(book), Some(name)) => {
+                output.push_str(&scripture[..book.0]);
+                output.push_str(name.canonical_name());
+                output.push_str(&scripture[book.1..]);
+            }
+            _ => output.push_str(scripture),
+        }
+    }
+
+    /// Returns the text field of the Script struct.
+    pub fn get_text(self) -> String {
+        self.string.into_owned()
+    }
+
+    /// Returns the text found within `slice`.
+    pub(crate) fn get_from_slice(&self, slice: &ScriptSlice) -> &str {
+        &self.string[slice.0..slice.1]
     }
 
     /// Returns a collection of scriptures found in the string passed in.
-    pub(crate) fn get_scriptures(&self) -> Result<ScriptureCollection, BibleError> {
+    pub fn get_scriptures(&self) -> Result<ScriptureCollection, BibleError> {
         let mut scripture_list:Vec<String>  = Vec::new();
 
         for i in self.slices.iter() {
             let scripture_str: &str = self.string.get(i.0..i.1).unwrap();
 
             // We need to validate if the found slice contains a valid Bible book name.
-            if Bible::parse(scripture_str).is_ok() {
+            if Bible::parse_with(scripture_str, self.lookup).is_ok() {
                 scripture_list.push(scripture_str.to_string());
             }
         }
@@ -175,9 +507,18 @@ impl<'a> Script<'a> {
         Ok(scripture_list)
     }
 
+    /// Returns a lazy iterator over the scriptures found, honoring the options set on the `Script`.
+    pub fn references(&self) -> References<'_> {
+        let references: References<'_> = References::new(&self.string, self.lookup);
+        match &self.prose {
+            Some(prose) => references.within(prose),
+            None => references,
+        }
+    }
+
     /// Returns the index of the start and end of each scripture found and also the original string.
-    pub(crate) fn get_locations(&self) -> Locations {
-        Locations { slices: self.slices.clone(), string: self.string.clone() }
+    pub fn get_locations(&self) -> Locations {
+        Locations { slices: self.slices.clone(), string: self.string.to_string() }
     }
 
 }
@@ -310,6 +651,202 @@ mod test {
         assert_eq!(got, expect)
     }
 
+    #[test]
+    fn surround_normalize() {
+        let text: &str = "SLIDE: 1 CORINTHIANS 13:4-7";
+        let expect: &str = "SLIDE: [1 Corinthians 13:4-7]";
+        let got: String = Script::new(text)
+            .prefix("[")
+            .postfix("]")
+            .normalize()
+            .surround()
+            .get_text();
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn url_ignore_diacritics() {
+        let text: &str = "Léase Ròmans 8:28.";
+        let expect: String = "Léase [Ròmans 8:28](https://www.jw.org/en/library/bible/study-bible/books/romans/8/#v45008028).".to_string();
+        let got: String = Script::new(text)
+            .ignore_diacritics()
+            .url(&Site::JwOrg)
+            .unwrap()
+            .get_text();
+        assert_eq!(got, expect)
+    }
+
+    #[test]
+    fn url_markdown_skips_links_and_code() {
+        let text: &str = "See [John 3:16](https://example.com) and `Ge 1:1`:\n\n```\nRe 21:4\n```\nthen Ps 83:18.";
+        let got: String = Script::new(text).markdown().url(&Site::JwOrg).unwrap().get_text();
+        let expect: String = "See [John 3:16](https://example.com) and `Ge 1:1`:\n\n```\nRe 21:4\n```\nthen [Ps 83:18](https://www.jw.org/en/library/bible/study-bible/books/psalms/83/#v19083018).".to_string();
+        assert_eq!(got, expect)
+    }
+
+    #[test]
+    fn references_markdown() {
+        let script: Script = Script::new("<p>John 3:16</p>\n\nRe 21:4 and ![Ge 1:1](image.png)").markdown();
+        let got: Vec<&str> = script.references().map(|found| found.as_str()).collect();
+        assert_eq!(got, vec!["Re 21:4"]);
+    }
+
+    #[test]
+    fn url_idempotent() {
+        let text: &str = "Read Joh 3:16 and [Ge 1:1](https://example.com/genesis).";
+        let once: String = Script::new(text).url(&Site::JwOrg).unwrap().get_text();
+        let twice: String = Script::new(once.as_str()).url(&Site::JwOrg).unwrap().get_text();
+        assert_eq!(once, "Read [Joh 3:16](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016) and [Ge 1:1](https://example.com/genesis).");
+        assert_eq!(twice, once);
+    }
+
+    #[test]
+    fn url_refresh_stale_link() {
+        let text: &str = "[JOHN 3:16](https://www.jw.org/en/library/bible/study-bible/books/matthew/24/#v40024014)";
+        let got: String = Script::new(text).normalize().url(&Site::JwOrg).unwrap().get_text();
+        let expect: &str = "[John 3:16](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016)";
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn url_link_text_around_scripture() {
+        let text: &str = "[see John 3:16](https://www.jw.org/en/library/bible/study-bible/books/matthew/24/#v40024014) and [read Ro 8:28 too](https://example.com).";
+        let got: String = Script::new(text).url(&Site::JwOrg).unwrap().get_text();
+        let expect: &str = "[see John 3:16](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016) and [read Ro 8:28 too](https://example.com).";
+        assert_eq!(got, expect);
+        let twice: String = Script::new(got.as_str()).url(&Site::JwOrg).unwrap().get_text();
+        assert_eq!(twice, expect);
+    }
+
+    #[test]
+    fn url_markdown_refresh_stale_link() {
+        let text: &str = "Read [see Joh 3:16 and 17](https://www.jw.org/en/library/bible/study-bible/books/matthew/24/#v40024014), [Ro 8:28](https://example.com) and Ps 83:18.";
+        let got: String = Script::new(text).markdown().normalize().url(&Site::JwOrg).unwrap().get_text();
+        let expect: &str = "Read [see John 3:16 and 17](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016), [Ro 8:28](https://example.com) and [Psalms 83:18](https://www.jw.org/en/library/bible/study-bible/books/psalms/83/#v19083018).";
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn html_normalize() {
+        let anchor: Anchor = Anchor::new().data_ref();
+        let got: String = Script::new("Read JOH 3:16 & ro 8:28.")
+            .normalize()
+            .html(&Site::JwOrg, &anchor)
+            .unwrap()
+            .get_text();
+        let expect: &str = r#"Read <a href="https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016" data-ref="John 3:16">John 3:16</a> & <a href="https://www.jw.org/en/library/bible/study-bible/books/romans/8/#v45008028" data-ref="Romans 8:28">Romans 8:28</a>."#;
+        assert_eq!(got, expect)
+    }
+
+    #[test]
+    fn html_idempotent() {
+        let anchor: Anchor = Anchor::new().data_ref();
+        let text: &str = r#"Read Joh 3:16 and <a href="https://example.com">Ge 1:1</a>."#;
+        let once: String = Script::new(text).html(&Site::JwOrg, &anchor).unwrap().get_text();
+        let twice: String = Script::new(once.as_str()).html(&Site::JwOrg, &anchor).unwrap().get_text();
+        let expect: &str = r#"Read <a href="https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016" data-ref="John 3:16">Joh 3:16</a> and <a href="https://example.com">Ge 1:1</a>."#;
+        assert_eq!(once, expect);
+        assert_eq!(twice, once);
+    }
+
+    #[test]
+    fn url_style() {
+        let got: String = Script::new("Read ro 8:28-29.")
+            .style(Style::Sbl)
+            .url(&Site::JwOrg)
+            .unwrap()
+            .get_text();
+        let expect: &str = "Read [Rom 8:28–29](https://www.jw.org/en/library/bible/study-bible/books/romans/8/#v45008028-v45008029).";
+        assert_eq!(got, expect)
+    }
+
+    #[test]
+    fn contains_slice() {
+        let ranges: [ScriptSlice; 3] = [(0, 7), (10, 20), (31, 40)];
+        let got: Vec<bool> = [(0, 5), (6, 9), (10, 20), (30, 35), (41, 45)]
+            .iter()
+            .map(|slice| contains(&ranges, slice))
+            .collect();
+        assert_eq!(got, vec![true, false, true, false, false]);
+    }
+
+    #[test]
+    fn html_document_text_nodes() {
+        let text: &str = "<h1 data-ref=\"Re 21:4\">Re 21:4</h1><script>\"Ro 8:28\"</script>&nbsp;Ro 8:28";
+        let got: String = Script::new(text)
+            .html_document()
+            .html(&Site::JwOrg, &Anchor::new())
+            .unwrap()
+            .get_text();
+        let expect: &str = r#"<h1 data-ref="Re 21:4"><a href="https://www.jw.org/en/library/bible/study-bible/books/revelation/21/#v66021004">Re 21:4</a></h1><script>"Ro 8:28"</script>&nbsp;<a href="https://www.jw.org/en/library/bible/study-bible/books/romans/8/#v45008028">Ro 8:28</a>"#;
+        assert_eq!(got, expect)
+    }
+
+    #[test]
+    fn url_latex() {
+        let got: String = Script::new("Read JOHN 3:16.")
+            .normalize()
+            .link_format(LinkFormat::Latex)
+            .url(&Site::JwOrg)
+            .unwrap()
+            .get_text();
+        let expect: &str = r"Read \href{https://www.jw.org/en/library/bible/study-bible/books/john/3/\#v43003016}{John 3:16}.";
+        assert_eq!(got, expect)
+    }
+
+    #[test]
+    fn url_idempotent_every_format() {
+        let formats: [LinkFormat; 6] = [
+            LinkFormat::Markdown,
+            LinkFormat::AsciiDoc,
+            LinkFormat::RestructuredText,
+            LinkFormat::Org,
+            LinkFormat::Latex,
+            LinkFormat::BBCode,
+        ];
+        for format in formats {
+            let link = |text: &str| {
+                Script::new(text).link_format(format).url(&Site::JwOrg).unwrap().get_text()
+            };
+            let once: String = link("Read Joh 3:16.");
+            assert_eq!(link(&once), once, "{format:?}");
+
+            // A stale link to the site is refreshed in place.
+            let stale: String = once.replace("john/3/", "matthew/24/").replace("43003016", "40024014");
+            assert_eq!(link(&stale), once, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn wiki_idempotent() {
+        let text: &str = "Read Joh 3:16 and Mary 1:1 [[Re 21:4]].";
+        let got = Script::new(text).wiki(&WikiLink::new());
+        assert_eq!(got.unwrap_err(), BibleError::BookNotFound("Mary".to_string()));
+
+        let once: String = Script::new("Read JOH 3:16; 4:1 and [[Re 21:4]].")
+            .normalize()
+            .wiki(&WikiLink::new())
+            .unwrap()
+            .get_text();
+        let twice: String = Script::new(once.as_str()).wiki(&WikiLink::new()).unwrap().get_text();
+        assert_eq!(once, "Read [[John 3#16|John 3:16; 4:1]] and [[Re 21:4]].");
+        assert_eq!(twice, once);
+    }
+
+    #[test]
+    fn url_diacritics_not_ignored() {
+        let text: &str = "Léase Ròmans 8:28.";
+        let got = Script::new(text).url(&Site::JwOrg);
+        assert_eq!(got.unwrap_err(), BibleError::BookNotFound("Ròmans".to_string()));
+    }
+
+    #[test]
+    fn find_slice_unicode() {
+        let text: &str = "Ἰωάννης 3:16 и Исаия 53:5";
+        let result: Script = Script::new(text);
+        assert_eq!(result.slices, vec![(0, 20), (24, 39)]);
+    }
+
     #[test]
     fn get_scripture() {
         let text: &str =
@@ -323,8 +860,9 @@ mod test {
     fn get_from_slice() {
         let text: &str =
             "Two popular scriptures are John 3:16 and Matthew 24:14. They are quoted often.";
-        let expect = "popular".to_string();
-        let got: String = Script::new(text).get_from_slice(&(4, 11));
+        let expect = "popular";
+        let script: Script = Script::new(text);
+        let got: &str = script.get_from_slice(&(4, 11));
         assert_eq!(got, expect)
     }
 
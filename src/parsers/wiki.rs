@@ -0,0 +1,95 @@
+use super::reference::Reference;
+
+/// The default note template, one note per chapter with a heading per verse.
+const TEMPLATE: &str = "{BOOK} {CHAPTER}#{VERSE}";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// _WikiLink_ describes the wiki links built around each scripture by [`crate::Script::wiki`], as used by
+/// Obsidian and Logseq, i.e. `[[John 3#16|John 3:16]]`.
+///
+/// The target of the link is built from a template with the following placeholders:
+/// - `{BOOK}`: the canonical name of the book, `1 Corinthians`
+/// - `{BOOKNUM}`: the number of the book, `46`
+/// - `{CHAPTER}`: the chapter, `13`
+/// - `{VERSE}`: the first verse, `4`
+///
+/// When a scripture has no verse, everything from the `#` in the template is left out.
+///
+/// ## Example
+/// ```
+/// use est::{Script, WikiLink};
+///
+/// let wiki: WikiLink = WikiLink::new().template("Bible/{BOOK}/{BOOK} {CHAPTER}#^v{VERSE}");
+/// let got: String = Script::new("Read 1 Co 13:4-7.").wiki(&wiki).unwrap().get_text();
+/// assert_eq!("Read [[Bible/1 Corinthians/1 Corinthians 13#^v4|1 Co 13:4-7]].", got);
+/// ```
+pub struct WikiLink<'a> {
+    template: &'a str,
+}
+
+impl Default for WikiLink<'_> {
+    fn default() -> Self {
+        Self { template: TEMPLATE }
+    }
+}
+
+impl<'a> WikiLink<'a> {
+    /// Links to a note per chapter with a heading per verse, `[[John 3#16|John 3:16]]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The template the target of each link is built from.
+    pub fn template(mut self, template: &'a str) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Pushes a wiki link to `reference` with `text` onto `output`.
+    pub(crate) fn push(&self, output: &mut String, reference: &Reference, text: &str) {
+        let template: &str = match reference.verses.first() {
+            Some(_) => self.template,
+            None => self.template.split('#').next().unwrap_or_default(),
+        };
+        let verse: String = reference
+            .verses
+            .first()
+            .map(|verse| verse.start.to_string())
+            .unwrap_or_default();
+
+        let target: String = template
+            .replace("{BOOKNUM}", &(reference.book as u8).to_string())
+            .replace("{BOOK}", reference.book.canonical_name())
+            .replace("{CHAPTER}", &reference.chapter.to_string())
+            .replace("{VERSE}", &verse);
+
+        output.push_str("[[");
+        output.push_str(&target);
+        output.push('|');
+        output.push_str(text);
+        output.push_str("]]");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn t_default_template() {
+        let mut output: String = String::new();
+        let reference: Reference = Reference::parse("joh 3:16-18").unwrap();
+        WikiLink::new().push(&mut output, &reference, "joh 3:16-18");
+        assert_eq!(output, "[[John 3#16|joh 3:16-18]]");
+    }
+
+    #[test]
+    fn t_no_verse() {
+        let mut output: String = String::new();
+        let reference: Reference = Reference::parse("Ps 23:").unwrap();
+        let wiki: WikiLink = WikiLink::new().template("{BOOKNUM}-{BOOK} {CHAPTER}#{VERSE}");
+        wiki.push(&mut output, &reference, "Ps 23:");
+        assert_eq!(output, "[[19-Psalms 23|Ps 23:]]");
+    }
+}
@@ -0,0 +1,360 @@
+//! Turns links built from the templates of a [`Site`], and links to the Bible on wol.jw.org, back into the
+//! references they point to.
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use crate::{
+    locales::{
+        nwt_en::{Book, Site, BOOKS},
+        BibleError,
+    },
+    parsers::{osis::push_span, reference::Reference},
+    url::{Url, PLACEHOLDER},
+};
+
+lazy_static! {
+    /// The templates of every supported site, for a range, a single verse and the page of a chapter.
+    static ref TEMPLATES: Vec<Template> = Site::ALL
+        .iter()
+        .flat_map(|site| {
+            let single: String = site.get_single();
+            let chapter: &str = single.split('#').next().unwrap_or_default();
+            [Template::new(&site.get_range()), Template::new(&single), Template::new(chapter)]
+        })
+        .collect();
+    /// A chapter of the Bible on wol.jw.org, with a verse or range in the `v=43:3:16-43:4:2` parameter.
+    static ref WOL: Regex = Regex::new(
+        r"^https://wol\.jw\.org/[^/]+/wol/b/[^/]+/[^/]+/[^/]+/(\d{1,2})/(\d{1,3})(?:[#?](?:.*?&)?v=(\d{1,2}):(\d{1,3}):(\d{1,3})(?:-(\d{1,2}):(\d{1,3}):(\d{1,3}))?)?$"
+    )
+    .expect("error while compiling the regex in WOL");
+}
+
+/// A placeholder of a URL template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    BookName,
+    BookNum,
+    Chapter,
+    Verse,
+}
+
+/// A URL template compiled into a regex, with the placeholder each group of the regex captures.
+struct Template {
+    regex: Regex,
+    fields: Vec<Field>,
+}
+
+impl Template {
+    /// Compiles `template`. The first chapter is written as is and the next ones with three digits, like the
+    /// verse, as they are by [`Url::get_url`].
+    fn new(template: &str) -> Self {
+        let mut pattern: String = String::from("^");
+        let mut fields: Vec<Field> = Vec::new();
+        let mut last: usize = 0;
+
+        for placeholder in PLACEHOLDER.captures_iter(template) {
+            let whole = placeholder.get(0).expect("a match always has a group 0");
+            pattern.push_str(&regex::escape(&template[last..whole.start()]));
+            last = whole.end();
+
+            let field: Field = match &placeholder[1] {
+                "BOOKNAME" => Field::BookName,
+                "BOOKNUM" => Field::BookNum,
+                "CHAPTER" => Field::Chapter,
+                _ => Field::Verse,
+            };
+            pattern.push_str(match field {
+                Field::BookName => r"([^/?#]+)",
+                Field::BookNum => r"(\d{1,2})",
+                Field::Chapter if !fields.contains(&Field::Chapter) => r"(\d{1,3})",
+                Field::Chapter | Field::Verse => r"(\d{3})",
+            });
+            fields.push(field);
+        }
+        pattern.push_str(&regex::escape(&template[last..]));
+        pattern.push('$');
+
+        Self {
+            regex: Regex::new(&pattern).expect("a URL template always compiles into a valid regex"),
+            fields,
+        }
+    }
+
+    /// Decodes `url`, `None` if the template does not match it.
+    fn decode(&self, url: &str) -> Option<Result<Vec<Reference>, BibleError>> {
+        let captures: Captures = self.regex.captures(url)?;
+        let mut decoded: Decoded = Decoded::default();
+
+        for (field, value) in self.fields.iter().zip(captures.iter().skip(1).flatten()) {
+            let value: &str = value.as_str();
+            let consistent: bool = match field {
+                Field::BookName => match book_named(value) {
+                    Some(book) => decoded.book(book),
+                    None => return Some(Err(BibleError::BookNotFound(value.to_string()))),
+                },
+                Field::BookNum => match book_numbered(value) {
+                    Ok(book) => decoded.book(book),
+                    Err(e) => return Some(Err(e)),
+                },
+                Field::Chapter => decoded.chapter(value),
+                Field::Verse => decoded.verse(value),
+            };
+            // A link with two different books, or whose first verse is not on the page, was not built from this
+            // template.
+            if !consistent {
+                return None;
+            }
+        }
+
+        Some(decoded.references(url))
+    }
+}
+
+/// The parts of a reference found in a link: the book, the chapter of the page and the chapter and verse of the
+/// first and last verse, if any.
+#[derive(Debug, Default)]
+struct Decoded {
+    book: Option<Book>,
+    page: Option<u16>,
+    chapter: Option<u16>,
+    verses: Vec<(u16, u16)>,
+}
+
+impl Decoded {
+    /// Sets the book, false if another book was found before.
+    fn book(&mut self, book: Book) -> bool {
+        *self.book.get_or_insert(book) == book
+    }
+
+    /// Sets the chapter of the page the first time, then the chapter of the next verse, false if it is not a
+    /// number.
+    fn chapter(&mut self, digits: &str) -> bool {
+        let Ok(chapter) = digits.parse::<u16>() else {
+            return false;
+        };
+        match self.page {
+            Some(_) => self.chapter = Some(chapter),
+            None => self.page = Some(chapter),
+        }
+        true
+    }
+
+    /// Adds a verse of the last chapter set, false if it is not a number or if the first verse is not on the page.
+    fn verse(&mut self, digits: &str) -> bool {
+        let (Ok(verse), Some(page)) = (digits.parse::<u16>(), self.page) else {
+            return false;
+        };
+        let chapter: u16 = self.chapter.unwrap_or(page);
+        self.verses.push((chapter, verse));
+        self.verses.len() > 1 || chapter == page
+    }
+
+    /// Returns one reference per chapter, from the first verse to the last one, or the chapter of the page.
+    fn references(self, url: &str) -> Result<Vec<Reference>, BibleError> {
+        let invalid = || BibleError::ParsingError(url.to_string());
+        let book: Book = self.book.ok_or_else(invalid)?;
+        let page: u16 = self.page.ok_or_else(invalid)?;
+
+        let (start, end) = match self.verses[..] {
+            [] => ((page, None), (page, None)),
+            [(chapter, verse)] => ((chapter, Some(verse)), (chapter, Some(verse))),
+            [start, end] if start <= end => ((start.0, Some(start.1)), (end.0, Some(end.1))),
+            [_, _] => return Err(BibleError::InvalidRange(url.to_string())),
+            _ => return Err(invalid()),
+        };
+
+        let mut references: Vec<Reference> = Vec::new();
+        push_span(&mut references, book, start, end)?;
+        Ok(references)
+    }
+}
+
+impl Reference {
+    /// Decodes a link to a scripture built for one of the supported sites, see [`Site::ALL`], or a link to the
+    /// Bible on wol.jw.org, into the references it points to, one per chapter like [`Reference::from_osis`]. A
+    /// link to a chapter gives a reference without verses, and a range that runs into the next chapter is split
+    /// at the last verse of the chapter.
+    ///
+    /// Decoding a pasted link and building it again for a site moves links from one site to another, and
+    /// [`Reference::display`] labels a bare link with the scripture it points to.
+    ///
+    /// ## Example
+    /// ```
+    /// use est::{Reference, VerseRange};
+    /// use est::locales::nwt_en::Book;
+    ///
+    /// let url: &str = "https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016-v43003018";
+    /// let got: Vec<Reference> = Reference::from_url(url).unwrap();
+    /// assert_eq!(got, vec![Reference { book: Book::John, chapter: 3, verses: vec![VerseRange { start: 16, end: 18 }] }]);
+    /// assert_eq!(got[0].to_string(), "John 3:16-18");
+    ///
+    /// let url: &str = "https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003036-v43004002";
+    /// let got: Vec<String> = Reference::from_url(url).unwrap().iter().map(Reference::to_string).collect();
+    /// assert_eq!(got, vec!["John 3:36", "John 4:1-2"]);
+    ///
+    /// let got: Vec<Reference> = Reference::from_url("https://wol.jw.org/en/wol/b/r1/lp-e/nwtsty/19/23").unwrap();
+    /// assert_eq!(got[0].to_string(), "Psalms 23");
+    /// ```
+    pub fn from_url(url: &str) -> Result<Vec<Reference>, BibleError> {
+        let url: &str = url.trim();
+
+        if let Some(decoded) = TEMPLATES.iter().find_map(|template| template.decode(url)) {
+            return decoded;
+        }
+        match WOL.captures(url) {
+            Some(captures) => wol(url, &captures),
+            None => Err(BibleError::ParsingError(url.to_string())),
+        }
+    }
+}
+
+/// Decodes the groups of [`WOL`]: the book and chapter of the page, then the book, chapter and verse of the
+/// first and last verse, if any.
+fn wol(url: &str, captures: &Captures) -> Result<Vec<Reference>, BibleError> {
+    let group = |i: usize| captures.get(i).map_or("", |group| group.as_str());
+    let mut decoded: Decoded = Decoded::default();
+
+    for (book, chapter, verse) in [(1, 2, None), (3, 4, Some(5)), (6, 7, Some(8))] {
+        if group(book).is_empty() {
+            continue;
+        }
+        let book: Book = book_numbered(group(book))?;
+        // A range into another book, or a first verse that is not on the page, is not a reference of the page.
+        if !decoded.book(book)
+            || !decoded.chapter(group(chapter))
+            || verse.is_some_and(|verse| !decoded.verse(group(verse)))
+        {
+            return Err(BibleError::ParsingError(url.to_string()));
+        }
+    }
+
+    decoded.references(url)
+}
+
+/// Returns the book named `name` in a URL, `john`, `1-corinthians` or `1%20corinthians`.
+fn book_named(name: &str) -> Option<Book> {
+    let name: String = name.to_lowercase().replace("%20", "-").replace(' ', "-");
+    BOOKS.into_iter().find(|book| <&str>::from(*book) == name)
+}
+
+/// Returns the book numbered `digits`, 1 for Genesis through 66 for Revelation.
+fn book_numbered(digits: &str) -> Result<Book, BibleError> {
+    let number: u8 = digits
+        .parse()
+        .map_err(|_| BibleError::BookNotFound(digits.to_string()))?;
+    Book::try_from(number)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::locales::nwt_en::Lookup;
+    use crate::parsers::reference::VerseRange;
+    use pretty_assertions::assert_eq;
+
+    fn reference(book: Book, chapter: u16, verses: &[(u16, u16)]) -> Vec<Reference> {
+        vec![Reference {
+            book,
+            chapter,
+            verses: verses
+                .iter()
+                .map(|(start, end)| VerseRange {
+                    start: *start,
+                    end: *end,
+                })
+                .collect(),
+        }]
+    }
+
+    #[test]
+    fn t_from_url_jw_org() {
+        let base: &str = "https://www.jw.org/en/library/bible/study-bible/books";
+        let got = |path: &str| Reference::from_url(&format!("{base}/{path}")).unwrap();
+        assert_eq!(
+            got("genesis/1/#v1001001"),
+            reference(Book::Genesis, 1, &[(1, 1)])
+        );
+        assert_eq!(
+            got("psalms/119/#v19119105"),
+            reference(Book::Psalms, 119, &[(105, 105)])
+        );
+        assert_eq!(
+            got("1-corinthians/13/#v46013004-v46013007"),
+            reference(Book::FirstCorinthians, 13, &[(4, 7)])
+        );
+        assert_eq!(got("john/3/"), reference(Book::John, 3, &[]));
+    }
+
+    #[test]
+    fn t_from_url_round_trip() {
+        let scriptures: [&str; 5] = [
+            "Genesis 1:1",
+            "Matthew 24:14-15",
+            "Revelation 22:21",
+            "John 3:16–18",
+            "Psalms 23:",
+        ];
+        for scripture in scriptures {
+            let url: String = crate::url(&Site::JwOrg, scripture).unwrap();
+            let url: &str = url.split_once("](").unwrap().1.trim_end_matches(')');
+            let got: Vec<Reference> = Reference::from_url(url).unwrap();
+            assert_eq!(
+                got,
+                Reference::parse_all(scripture, Lookup::default()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn t_from_url_wol() {
+        let base: &str = "https://wol.jw.org/en/wol/b/r1/lp-e/nwtsty/43/3";
+        let got = |fragment: &str| Reference::from_url(&format!("{base}{fragment}"));
+        assert_eq!(got(""), Ok(reference(Book::John, 3, &[])));
+        assert_eq!(
+            got("#study=discover&v=43:3:16"),
+            Ok(reference(Book::John, 3, &[(16, 16)]))
+        );
+        assert_eq!(
+            got("#study=discover&v=43:3:16-43:3:18"),
+            Ok(reference(Book::John, 3, &[(16, 18)]))
+        );
+        assert_eq!(
+            got("#v=43:3:36-43:4:2"),
+            Ok(vec![
+                reference(Book::John, 3, &[(36, 36)]).remove(0),
+                reference(Book::John, 4, &[(1, 2)]).remove(0),
+            ])
+        );
+        assert_eq!(
+            got("#v=43:4:1"),
+            Err(BibleError::ParsingError(format!("{base}#v=43:4:1")))
+        );
+        assert_eq!(
+            got("#v=43:3:16-44:1:1"),
+            Err(BibleError::ParsingError(format!("{base}#v=43:3:16-44:1:1")))
+        );
+    }
+
+    #[test]
+    fn t_from_url_errors() {
+        let base: &str = "https://www.jw.org/en/library/bible/study-bible/books";
+        let parse = |url: &str| Reference::from_url(url).unwrap_err();
+        assert_eq!(
+            parse("https://example.com/john/3/#v43003016"),
+            BibleError::ParsingError("https://example.com/john/3/#v43003016".to_string())
+        );
+        assert_eq!(
+            parse(&format!("{base}/mary/1/")),
+            BibleError::BookNotFound("mary".to_string())
+        );
+        assert_eq!(
+            parse(&format!("{base}/john/3/#v43003018-v43003016")),
+            BibleError::InvalidRange(format!("{base}/john/3/#v43003018-v43003016"))
+        );
+        // The anchor points to another chapter than the page.
+        assert_eq!(
+            parse(&format!("{base}/john/3/#v43004001")),
+            BibleError::ParsingError(format!("{base}/john/3/#v43004001"))
+        );
+    }
+}
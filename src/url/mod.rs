@@ -3,20 +3,19 @@ use crate::{
         nwt_en::{Book, UrlTemplate},
         BibleError,
     },
-    parsers::scripture::Bible,
+    parsers::{
+        bcv::{bcv, BOOK, CHAPTER},
+        reference::Reference,
+    },
 };
 use lazy_static::lazy_static;
 use regex::Regex;
 
+mod decode;
+
 lazy_static! {
-    pub static ref BOOKNAME: regex::Regex =
-        Regex::new(r"\{BOOKNAME\}").expect("error while compiling the regex in BOOKNAME");
-    pub static ref CHAPTER: regex::Regex =
-        Regex::new(r"\{CHAPTER\}").expect("error while compiling the regex in CHAPTER");
-    pub static ref BOOKNUM: regex::Regex =
-        Regex::new(r"\{BOOKNUM\}").expect("error while compiling the regex in BOOKNUM");
-    pub static ref VERSE: regex::Regex =
-        Regex::new(r"\{VERSE\}").expect("error while compiling the regex in VERSE");
+    pub static ref PLACEHOLDER: Regex = Regex::new(r"\{(BOOKNAME|BOOKNUM|CHAPTER|VERSE)\}")
+        .expect("error while compiling the regex in PLACEHOLDER");
 }
 pub trait Url {
     /// Returns the template for constructing the URL.
@@ -26,88 +25,80 @@ pub trait Url {
     /// Returns the template for a ranged verse.
     fn get_range(&self) -> String;
 
-    fn get_url(&self, scripture: &Bible) -> Result<String, BibleError> {
-        match scripture.is_range() {
-            true => self.get_url_verse_range(scripture),
-            false => self.get_url_verse_single(scripture),
-        }
-    }
-
-    /// Constructs the proper URL from `url_template` when there's a single verse
-    fn get_url_verse_single(&self, scripture: &Bible) -> Result<String, BibleError> {
-        let book_name: Book = scripture.get_book().try_into().unwrap();
-        let book_name: &str = book_name.into();
-
-        let url: String = crate::url::BOOKNAME
-            .replace(&self.get_single(), book_name)
-            .into();
-
-        let url: String = crate::url::CHAPTER
-            .replace(&url, scripture.get_chapter())
-            .into();
-
-        let url: String = crate::url::BOOKNUM
-            .replace(&url, format!("{:0<2}", scripture.get_idx()?))
-            .into();
-
-        let url: String = crate::url::CHAPTER
-            .replace(&url, format!("{:0>3}", scripture.get_chapter()))
-            .into();
-
-        let url: String = crate::url::VERSE
-            .replace(&url, format!("{:0>3}", scripture.get_verse()))
-            .into();
+    /// Constructs the URL of `reference`, linking its first verse or range of verses, or the page of the chapter
+    /// when it has no verses. The other verses of a list are not linked, so no verse that was not cited is
+    /// highlighted. A chapter or verse that does not fit in the verse id of the link is an error.
+    fn get_url(&self, reference: &Reference) -> Result<String, BibleError> {
+        let Some(verses) = reference.verses.first() else {
+            let single: String = self.get_single();
+            let chapter: &str = single.split('#').next().unwrap_or_default();
+            return Ok(fill(chapter, reference.book, reference.chapter, &[]));
+        };
 
-        Ok(url)
+        let start: u32 = bcv(reference.book, reference.chapter, verses.start)?;
+        let end: u32 = bcv(reference.book, reference.chapter, verses.end)?;
+        Ok(match start == end {
+            true => fill(
+                &self.get_single(),
+                reference.book,
+                reference.chapter,
+                &[start],
+            ),
+            false => fill(
+                &self.get_range(),
+                reference.book,
+                reference.chapter,
+                &[start, end],
+            ),
+        })
     }
+}
 
-    /// Constructs the proper URL from `url_template` when the verse is part of a range.
-    fn get_url_verse_range(&self, scripture: &Bible) -> Result<String, BibleError> {
-        let book_name: Book = scripture.get_book().try_into().unwrap();
-        let book_name: &str = book_name.into();
-
-        let url: String = crate::url::BOOKNAME
-            .replace(&self.get_range(), book_name)
-            .into();
-
-        let url: String = crate::url::CHAPTER
-            .replace(&url, scripture.get_chapter())
-            .into();
-
-        let url: String = crate::url::BOOKNUM
-            .replace(&url, format!("{:0<2}", scripture.get_idx()?))
-            .into();
-
-        let url: String = crate::url::CHAPTER
-            .replace(&url, format!("{:0>3}", scripture.get_chapter()))
-            .into();
-
-        if let Some((verse_one, verse_two)) = &scripture.get_verse().split_once('-') {
-            let url: String = crate::url::VERSE
-                .replace(&url, format!("{:0>3}", verse_one))
-                .into();
-            let url: String = crate::url::BOOKNUM
-                .replace(&url, format!("{:0<2}", scripture.get_idx()?))
-                .into();
-            let url: String = crate::url::CHAPTER
-                .replace(&url, format!("{:0>3}", scripture.get_chapter()))
-                .into();
-            let url: String = crate::url::VERSE
-                .replace(&url, format!("{:0>3}", verse_two))
-                .into();
-            return Ok(url);
-        };
-        Ok(url)
+/// Fills the placeholders of `template` with `book`, the `chapter` of the page and the BCV numbers of `verses`.
+/// The first `{CHAPTER}` is the chapter of the page, written as is. Each verse id after it is written as
+/// `{BOOKNUM}{CHAPTER}{VERSE}`, the chapter and verse with three digits, taken from the next BCV number.
+fn fill(template: &str, book: Book, chapter: u16, verses: &[u32]) -> String {
+    let mut url: String = String::with_capacity(template.len());
+    let mut verses = verses.iter().copied();
+    let mut verse: u32 = 0;
+    let mut page: bool = true;
+    let mut last: usize = 0;
+
+    for placeholder in PLACEHOLDER.captures_iter(template) {
+        let whole = placeholder.get(0).expect("a match always has a group 0");
+        url.push_str(&template[last..whole.start()]);
+        last = whole.end();
+
+        match &placeholder[1] {
+            "BOOKNAME" => url.push_str(book.into()),
+            "BOOKNUM" => {
+                verse = verses.next().unwrap_or(verse);
+                url.push_str(&(verse / BOOK).to_string());
+            }
+            "CHAPTER" if page => {
+                url.push_str(&chapter.to_string());
+                page = false;
+            }
+            "CHAPTER" => url.push_str(&format!("{:0>3}", verse / CHAPTER % CHAPTER)),
+            _ => url.push_str(&format!("{:0>3}", verse % CHAPTER)),
+        }
     }
+    url.push_str(&template[last..]);
+
+    url
 }
 
 #[cfg(test)]
 mod test {
 
     use super::*;
-    use crate::locales::nwt_en::Site;
+    use crate::locales::nwt_en::{Lookup, Site};
     use pretty_assertions::assert_eq;
 
+    fn reference(scripture: &str) -> Reference {
+        Reference::parse_with(scripture, Lookup::default()).unwrap()
+    }
+
     #[test]
     fn test_url_template_jw_org_single() {
         let site: Site = Site::JwOrg;
@@ -126,9 +117,9 @@ mod test {
 
     #[test]
     fn test_get_url_jw_org_matthew() {
-        let scripture: Bible = Bible::parse("matthew 24:14").unwrap();
+        let scripture: Reference = reference("matthew 24:14");
         let site: Site = Site::JwOrg;
-        let result: String = site.get_url_verse_single(&scripture).unwrap();
+        let result: String = site.get_url(&scripture).unwrap();
         let expected: String =
             "https://www.jw.org/en/library/bible/study-bible/books/matthew/24/#v40024014".into();
         assert_eq!(result, expected);
@@ -136,9 +127,9 @@ mod test {
 
     #[test]
     fn test_get_url_jw_org_matthew_range() {
-        let scripture: Bible = Bible::parse("matthew 24:14-15").unwrap();
+        let scripture: Reference = reference("matthew 24:14-15");
         let site: Site = Site::JwOrg;
-        let result: String = site.get_url_verse_range(&scripture).unwrap();
+        let result: String = site.get_url(&scripture).unwrap();
         let expected: String =
             "https://www.jw.org/en/library/bible/study-bible/books/matthew/24/#v40024014-v40024015"
                 .into();
@@ -147,21 +138,66 @@ mod test {
 
     #[test]
     fn test_get_url_jw_org_john() {
-        let scripture: Bible = Bible::parse("john 3:16").unwrap();
+        let scripture: Reference = reference("john 3:16");
         let site: Site = Site::JwOrg;
-        let got: String = site.get_url_verse_single(&scripture).unwrap();
+        let got: String = site.get_url(&scripture).unwrap();
         let expect: String =
             "https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016".into();
         assert_eq!(got, expect);
     }
 
+    #[test]
+    fn test_get_url_jw_org_book_number() {
+        let site: Site = Site::JwOrg;
+        let got = |scripture: &str| site.get_url(&reference(scripture)).unwrap();
+        assert_eq!(
+            got("genesis 1:1-3"),
+            "https://www.jw.org/en/library/bible/study-bible/books/genesis/1/#v1001001-v1001003"
+        );
+        assert_eq!(
+            got("exodus 3:14"),
+            "https://www.jw.org/en/library/bible/study-bible/books/exodus/3/#v2003014"
+        );
+    }
+
     #[test]
     fn test_get_url_jw_org_john_abbr() {
-        let scripture: Bible = Bible::parse("joh 3:16").unwrap();
+        let scripture: Reference = reference("joh 3:16");
         let site: Site = Site::JwOrg;
-        let got: String = site.get_url_verse_single(&scripture).unwrap();
+        let got: String = site.get_url(&scripture).unwrap();
         let expect: String =
             "https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016".into();
         assert_eq!(got, expect);
     }
+
+    #[test]
+    fn test_get_url_jw_org_lists() {
+        let site: Site = Site::JwOrg;
+        let got = |scripture: &str| site.get_url(&reference(scripture)).unwrap();
+        let base: &str = "https://www.jw.org/en/library/bible/study-bible/books/john";
+        assert_eq!(got("John 3:16, 18"), format!("{base}/3/#v43003016"));
+        assert_eq!(got("John 3:16; 4:1"), format!("{base}/3/#v43003016"));
+        assert_eq!(
+            got("John 3:16–18"),
+            format!("{base}/3/#v43003016-v43003018")
+        );
+        assert_eq!(
+            got("John 3:16-18, 20"),
+            format!("{base}/3/#v43003016-v43003018")
+        );
+        assert_eq!(got("John 3:"), format!("{base}/3/"));
+    }
+
+    #[test]
+    fn test_get_url_out_of_range() {
+        let site: Site = Site::JwOrg;
+        assert_eq!(
+            site.get_url(&reference("John 9999:1")),
+            Err(BibleError::ChapterNotFound("John".to_string(), 9999))
+        );
+        assert_eq!(
+            site.get_url(&reference("John 3:1000")),
+            Err(BibleError::VerseNotFound("John".to_string(), 3, 1000))
+        );
+    }
 }
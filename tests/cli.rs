@@ -0,0 +1,169 @@
+//! Runs the `est` binary, only built with the `cli` feature.
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use pretty_assertions::assert_eq;
+
+fn est(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_est"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn scan_stdin(){
+    let output: Output = est(&["scan"], "Read John 3:16\nand Mary 1:1, Ro 8:28.");
+    assert!(output.status.success());
+    assert_eq!("<stdin>:1:6: John 3:16\n<stdin>:2:15: Ro 8:28\n", String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn link_format(){
+    let output: Output = est(&["link", "--format", "org", "--normalize"], "Read JOH 3:16.");
+    assert_eq!("Read [[https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016][John 3:16]].", String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn link_format_twice(){
+    for format in ["markdown", "asciidoc", "rst", "org", "latex", "bbcode"] {
+        let once: String = String::from_utf8(est(&["link", "--format", format], "Read John 3:16.").stdout).unwrap();
+        let twice: String = String::from_utf8(est(&["link", "--format", format], &once).stdout).unwrap();
+        assert_eq!(once, twice, "{format}");
+    }
+}
+
+#[test]
+fn link_unknown_book(){
+    let output: Output = est(&["link"], "Read Mary 1:1.");
+    assert!(!output.status.success());
+    assert_eq!("est: <stdin>: the Bible book, Mary, was not found\n", String::from_utf8(output.stderr).unwrap());
+}
+
+#[test]
+fn surround_in_place(){
+    let dir = std::env::temp_dir().join(format!("est-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("notes.md");
+    fs::write(&file, "Read John 3:16, not `Ro 8:28`.").unwrap();
+
+    let output: Output = est(&["surround", "--prefix", "**", "--postfix", "**", "--input", "markdown", "-i", file.to_str().unwrap()], "");
+    assert!(output.status.success());
+    assert_eq!("Read **John 3:16**, not `Ro 8:28`.", fs::read_to_string(&file).unwrap());
+    assert_eq!("Read John 3:16, not `Ro 8:28`.", fs::read_to_string(dir.join("notes.md.bak")).unwrap());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn scan_ndjson(){
+    let output: Output = est(&["scan", "--all", "--output", "ndjson"], "Read Ro 8:28 and Mary 1:1");
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stdout).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(2, lines.len());
+    assert_eq!(45, lines[0]["book"]);
+    assert_eq!("Romans", lines[0]["book_name"]);
+    assert_eq!(6, lines[0]["start"]["column"]);
+    assert_eq!(6, lines[0]["start"]["utf16_column"]);
+    assert_eq!("https://www.jw.org/en/library/bible/study-bible/books/romans/8/#v45008028", lines[0]["url"]);
+    assert_eq!("Rom.8.28", lines[0]["osis"]);
+    assert_eq!(false, lines[1]["valid"]);
+}
+
+#[test]
+fn link_directory_dry_run(){
+    let dir = std::env::temp_dir().join(format!("est-cli-walk-{}", std::process::id()));
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::create_dir_all(dir.join("drafts")).unwrap();
+    fs::write(dir.join("sub/a.md"), "Read Ro 8:28.\n").unwrap();
+    fs::write(dir.join("drafts/b.md"), "Read Ro 8:28.\n").unwrap();
+    fs::write(dir.join("c.txt"), "Read Ro 8:28.\n").unwrap();
+    fs::write(dir.join("d.md"), b"Ro 8:28\0").unwrap();
+
+    let output: Output = est(&["link", "--dry-run", "--include", "*.md", "--exclude", "drafts/**", dir.to_str().unwrap()], "");
+    assert!(output.status.success());
+    let file = dir.join("sub/a.md").display().to_string();
+    assert_eq!(format!("--- {file}\n+++ {file}\n@@ -1 +1 @@\n-Read Ro 8:28.\n+Read [Ro 8:28](https://www.jw.org/en/library/bible/study-bible/books/romans/8/#v45008028).\n"), String::from_utf8(output.stdout).unwrap());
+    assert_eq!(format!("est: {}: skipping binary file\nest: {file}: 1 line changed\nest: 1 of 1 file changed\n", dir.join("d.md").display()), String::from_utf8(output.stderr).unwrap());
+    assert_eq!("Read Ro 8:28.\n", fs::read_to_string(dir.join("sub/a.md")).unwrap());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn check_errors(){
+    let output: Output = est(&["check"], "Read Phil 1:1, John 3:16-12\nand Jude 2:1, Ro 8:28.");
+    assert!(!output.status.success());
+    assert_eq!("<stdin>:1:6: error: the Bible book, Phil, is ambiguous, it could be Philippians, Philemon\n<stdin>:1:16: error: the verse range, John 3:16-12, ends before it starts\n<stdin>:2:5: error: Jude has no chapter 2\n", String::from_utf8(output.stdout).unwrap());
+
+    let output: Output = est(&["check", "--output", "sarif"], "Read Ro 8:40.");
+    let sarif: serde_json::Value = serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!("verse-not-found", result["ruleId"]);
+    assert_eq!(6, result["locations"][0]["physicalLocation"]["region"]["startColumn"]);
+    assert!(est(&["check"], "Read Ro 8:28.").status.success());
+}
+
+#[test]
+fn normalize_style(){
+    let output: Output = est(&["normalize", "--style", "nwt"], "Read john 3:16—18,20 and ROMANS 8:28.");
+    assert!(output.status.success());
+    assert_eq!("Read Joh 3:16-18, 20 and Ro 8:28.", String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn normalize_usfm(){
+    let output: Output = est(&["normalize", "--usfm", "--style", "full"], "Read JHN 3:16 and 1CO 13:4.");
+    assert_eq!("Read John 3:16 and 1 Corinthians 13:4.", String::from_utf8(output.stdout).unwrap());
+
+    let output: Output = est(&["normalize", "--style", "usfm"], "Read Joh 3:16.");
+    assert_eq!("Read JHN 3:16.", String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn normalize_then_check(){
+    let output: Output = est(&["normalize", "--style", "full"], "Read Ca 2:1 and 1 Co 13:4.");
+    let normalized: String = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("Read Song of Solomon 2:1 and 1 Corinthians 13:4.", normalized);
+
+    let output: Output = est(&["check"], &normalized);
+    assert!(output.status.success(), "{}", String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn normalize_sbl_then_link(){
+    let output: Output = est(&["normalize", "--style", "sbl"], "Read ge 1:1 and Romans 8:28.");
+    let normalized: String = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("Read Gen 1:1 and Rom 8:28.", normalized);
+
+    let output: Output = est(&["link", "--sbl", "--normalize"], &normalized);
+    assert!(output.status.success());
+    assert_eq!("Read [Genesis 1:1](https://www.jw.org/en/library/bible/study-bible/books/genesis/1/#v1001001) and [Romans 8:28](https://www.jw.org/en/library/bible/study-bible/books/romans/8/#v45008028).", String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn link_directory_twice(){
+    let dir = std::env::temp_dir().join(format!("est-cli-twice-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.md"), "Read Ro 8:28.\n").unwrap();
+    fs::write(dir.join("b.txt"), b"Ro 8:28 \xff\n").unwrap();
+
+    let (a, b) = (dir.join("a.md").display().to_string(), dir.join("b.txt").display().to_string());
+    let invalid: String = format!("est: {b}: invalid utf-8 sequence of 1 bytes from index 8\n");
+    let output: Output = est(&["link", "-i", dir.to_str().unwrap()], "");
+    assert!(!output.status.success());
+    assert_eq!(format!("est: {a}: 1 line changed\n{invalid}est: 1 of 1 file changed\n"), String::from_utf8(output.stderr).unwrap());
+
+    let output: Output = est(&["link", "-i", dir.to_str().unwrap()], "");
+    assert_eq!(format!("{invalid}est: 0 of 1 file changed\n"), String::from_utf8(output.stderr).unwrap());
+    let mut files: Vec<String> = fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name().into_string().unwrap()).collect();
+    files.sort();
+    assert_eq!(vec!["a.md", "a.md.bak", "b.txt"], files);
+    assert_eq!("Read Ro 8:28.\n", fs::read_to_string(dir.join("a.md.bak")).unwrap());
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -1,3 +1,4 @@
+#[allow(clippy::single_component_path_imports)]
 use est;
 use est::locales::nwt_en::Site::JwOrg;
 use est::Locations;
@@ -52,6 +53,12 @@ fn get_scripts_7(){
     let expected:Vec<String> = vec!["Psalms 3:1-3".into()];
     assert_eq!(expected, est::get_scriptures(text).unwrap());
 }
+#[test]
+fn get_scripts_8(){
+    let text: &str = "JOHN 3:16 and 1 CORINTHIANS 13:4-7";
+    let expected:Vec<String> = vec!["JOHN 3:16".into(), "1 CORINTHIANS 13:4-7".into()];
+    assert_eq!(expected, est::get_scriptures(text).unwrap());
+}
 
 
 #[test]
@@ -84,6 +91,12 @@ fn surround_scripts_5(){
     let expected:String = "All friends should practice <bold>Proverbs 17:17-19</bold>!".into();
     assert_eq!(expected, est::surround(text, "<bold>", "</bold>").unwrap());
 }
+#[test]
+fn surround_scripts_6(){
+    let text: &str = "JOHN 3:16 — GOD'S LOVE";
+    let expected:String = "<bold>John 3:16</bold> — GOD'S LOVE".into();
+    assert_eq!(expected, est::surround_normalized(text, "<bold>", "</bold>").unwrap());
+}
 
 
 #[test]
@@ -105,4 +118,137 @@ fn location2(){
     let text= "This string contains no scriptures.";
     let expected: Locations = Locations { slices: vec![], string: text.into() }; 
     assert_eq!(expected, est::get_locations(text));
-}
\ No newline at end of file
+}
+
+#[test]
+fn location3(){
+    let text= "Lea Génesis 1:1.";
+    let expected: Locations = Locations { slices: vec![(4,16)], string: text.into() }; 
+    assert_eq!(expected, est::get_locations(text));
+}
+
+#[test]
+fn script_ignore_diacritics(){
+    let text: &str = "Lea Génesis 1:1 y Mateo 24:14.";
+    let expected:Vec<String> = vec!["Génesis 1:1".into()];
+    assert_eq!(expected, est::Script::new(text).ignore_diacritics().get_scriptures().unwrap());
+}
+
+#[test]
+fn location_spans(){
+    let text= "Ünïcödé notes\nsee Re 12:12";
+    let spans: Vec<est::Span> = est::get_locations(text).spans();
+    let expected: est::Position = est::Position { byte: 22, char: 18, utf16: 18, line: 2, column: 5, utf16_column: 5 };
+    assert_eq!(1, spans.len());
+    assert_eq!(expected, spans[0].start);
+}
+
+
+#[test]
+fn references_lazy(){
+    let text: &str = "Two popular scriptures are Genesis 1:1 and John 3:16, they are quoted often.";
+    let found: Vec<&str> = est::references(text).filter(|m| m.is_valid()).map(|m| m.as_str()).collect();
+    assert_eq!(vec!["Genesis 1:1", "John 3:16"], found);
+}
+
+
+#[test]
+fn surround_stream_large(){
+    let text: String = "Two popular scriptures are Genesis 1:1 and John 3:16, they are quoted often.\n".repeat(2000);
+    let mut output: Vec<u8> = Vec::new();
+    est::surround_stream(text.as_bytes(), &mut output, "**", "**").unwrap();
+    assert_eq!(est::surround(text, "**", "**").unwrap().into_bytes(), output);
+}
+
+
+#[test]
+fn url_markdown(){
+    let text: &str = "Two popular scriptures are [Genesis 1:1](https://example.com) and `John 3:16`, but Re 21:4 is linked.";
+    let expected: String = "Two popular scriptures are [Genesis 1:1](https://example.com) and `John 3:16`, but [Re 21:4](https://www.jw.org/en/library/bible/study-bible/books/revelation/21/#v66021004) is linked.".to_string();
+    assert_eq!(expected, est::url_markdown(&JwOrg, text).unwrap());
+}
+
+#[test]
+fn url_markdown_inline_html(){
+    let text: &str = "Read <a href=\"https://example.com\">John 3:16</a> and <code>Ge 1:1</code>, not Re 21:4.";
+    let expected: String = "Read <a href=\"https://example.com\">John 3:16</a> and <code>Ge 1:1</code>, not [Re 21:4](https://www.jw.org/en/library/bible/study-bible/books/revelation/21/#v66021004).".to_string();
+    assert_eq!(expected, est::url_markdown(&JwOrg, text).unwrap());
+}
+
+#[test]
+fn url_twice(){
+    let text: &str = "Two popular scriptures are Genesis 1:1 and John 3:16, they are quoted often.";
+    let once: String = est::url(&JwOrg, text).unwrap();
+    assert_eq!(once, est::url(&JwOrg, once.as_str()).unwrap());
+}
+
+#[test]
+fn html_anchor(){
+    let anchor = est::Anchor::new().class("verse").target("_blank");
+    let got: String = est::Script::new("Read John 3:16.").html(&JwOrg, &anchor).unwrap().get_text();
+    assert_eq!(r#"Read <a href="https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016" class="verse" target="_blank" rel="noopener noreferrer">John 3:16</a>."#, got);
+}
+
+#[test]
+fn normalize_twice(){
+    let text: &str = "Read joh 3:16—18,20; 4:1 and Mary 1:1.";
+    let once: String = est::normalize(text, est::Style::Nwt).unwrap();
+    assert_eq!("Read Joh 3:16-18, 20; 4:1 and Mary 1:1.", once);
+    assert_eq!(once, est::normalize(once.as_str(), est::Style::Nwt).unwrap());
+}
+
+#[test]
+fn osis_round_trip(){
+    let osis: Vec<String> = est::references("Read 1 Co 13:4-7, 13 and Ps 23: and Jude 3:").map(|found| found.reference().unwrap().to_osis()).collect();
+    assert_eq!(vec!["1Cor.13.4-1Cor.13.7 1Cor.13.13", "Ps.23", "Jude.3"], osis);
+    for id in osis {
+        let references: Vec<est::Reference> = est::Reference::from_osis(&id).unwrap();
+        assert_eq!(id, references[0].to_osis());
+    }
+}
+
+#[test]
+fn migrate_wol_link(){
+    let pasted: &str = "https://wol.jw.org/en/wol/b/r1/lp-e/nwtsty/43/3#study=discover&v=43:3:16-43:3:18";
+    let references: Vec<est::Reference> = est::Reference::from_url(pasted).unwrap();
+    let expected: &str = "[John 3:16-18](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003016-v43003018)";
+    assert_eq!(expected, est::url(&JwOrg, references[0].to_string()).unwrap());
+}
+
+#[test]
+fn migrate_wol_link_across_chapters(){
+    let pasted: &str = "https://wol.jw.org/en/wol/b/r1/lp-e/nwtsty/43/3#study=discover&v=43:3:36-43:4:2";
+    let scripture: String = est::Reference::from_url(pasted).unwrap().iter().map(|reference| reference.to_string()).collect::<Vec<String>>().join("; ");
+    assert_eq!("John 3:36; John 4:1-2", scripture);
+    let expected: &str = "[John 3:36](https://www.jw.org/en/library/bible/study-bible/books/john/3/#v43003036); [John 4:1-2](https://www.jw.org/en/library/bible/study-bible/books/john/4/#v43004001-v43004002)";
+    assert_eq!(expected, est::url(&JwOrg, scripture).unwrap());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn batch_get_scriptures(){
+    let notes: Vec<String> = vec!["Genesis 1:1 and John 3:16".to_string(), "No scripture".to_string(), "Mary 1:1".to_string()];
+    let got = est::batch::get_scriptures(&notes);
+    assert_eq!(Ok(vec!["Genesis 1:1".to_string(), "John 3:16".to_string()]), got[0]);
+    assert_eq!(Ok(vec![]), got[1]);
+    assert_eq!(Ok(vec![]), got[2]);
+}
+
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_reference(){
+    let reference: est::Reference = est::Reference::parse("1 Co 13:4-7").unwrap();
+    let json: String = serde_json::to_string(&reference).unwrap();
+    assert_eq!(r#"{"book":"FirstCorinthians","chapter":13,"verses":[{"start":4,"end":7}]}"#, json);
+    assert_eq!(reference, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn normalize_sbl_round_trip(){
+    let text: &str = "Read Ca 2:1–3, Job 1:1 and 1 Co 13:4.";
+    let sbl: String = est::normalize(text, est::Style::Sbl).unwrap();
+    assert_eq!("Read Song 2:1–3, Job 1:1 and 1 Cor 13:4.", sbl);
+    let full: String = est::Script::new(sbl.as_str()).sbl().style(est::Style::Full).surround().get_text();
+    assert_eq!("Read Song of Solomon 2:1-3, Job 1:1 and 1 Corinthians 13:4.", full);
+}